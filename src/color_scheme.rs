@@ -0,0 +1,154 @@
+use crate::generic_cube::{Face, ORDERED_FACES};
+use crate::facelet_cube::FaceletCube;
+
+/// A named sticker colour.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Color {
+    /// The name of the colour.
+    pub name: String,
+    /// The letter used for the colour when writing out cube states.
+    pub letter: char,
+    /// The red, green and blue components of the colour.
+    pub rgb: [u8; 3],
+}
+
+impl Color {
+    /// Creates a colour from its name, letter and RGB value.
+    pub fn new(name: &str, letter: char, rgb: [u8; 3]) -> Self {
+        Self { name: String::from(name), letter, rgb }
+    }
+
+    /// The standard white sticker colour.
+    pub fn white() -> Self { Self::new("white", 'W', [255, 255, 255]) }
+
+    /// The standard yellow sticker colour.
+    pub fn yellow() -> Self { Self::new("yellow", 'Y', [255, 213, 0]) }
+
+    /// The standard green sticker colour.
+    pub fn green() -> Self { Self::new("green", 'G', [0, 155, 72]) }
+
+    /// The standard blue sticker colour.
+    pub fn blue() -> Self { Self::new("blue", 'B', [0, 70, 173]) }
+
+    /// The standard red sticker colour.
+    pub fn red() -> Self { Self::new("red", 'R', [183, 18, 52]) }
+
+    /// The standard orange sticker colour.
+    pub fn orange() -> Self { Self::new("orange", 'O', [255, 88, 0]) }
+
+    /// The standard gray sticker colour.
+    pub fn gray() -> Self { Self::new("gray", 'X', [128, 128, 128]) }
+
+    /// The colour as a hexadecimal string such as ``#ff5800``.
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.rgb[0], self.rgb[1], self.rgb[2])
+    }
+}
+
+/// A mapping from each face of a Rubik's Cube to the colour of its stickers.
+///
+/// The ``Face`` enum describes where a sticker belongs rather than what it looks like,
+/// so a colour scheme is needed to talk about the actual colours of a physical cube.
+/// Masked stickers are given a separate colour, which is gray by default.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Face;
+/// use cubesim::{Color, ColorScheme};
+///
+/// let scheme = ColorScheme::western();
+/// assert_eq!(scheme.color(Face::U), &Color::white());
+/// assert_eq!(scheme.color(Face::F), &Color::green());
+/// assert_eq!(scheme.face('O'), Some(Face::L));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ColorScheme {
+    colors: [Color; 6],
+    masked: Color,
+}
+
+impl ColorScheme {
+    /// Creates a colour scheme from the colours of the U, R, F, D, L and B faces, in that order.
+    pub fn new(colors: [Color; 6]) -> Self {
+        Self { colors, masked: Color::gray() }
+    }
+
+    /// The standard Western colour scheme, with white on top and green in front.
+    pub fn western() -> Self {
+        Self::new([Color::white(), Color::red(), Color::green(), Color::yellow(), Color::orange(), Color::blue()])
+    }
+
+    /// The Japanese colour scheme, with white on top, green in front and blue on the bottom.
+    pub fn japanese() -> Self {
+        Self::new([Color::white(), Color::red(), Color::green(), Color::blue(), Color::orange(), Color::yellow()])
+    }
+
+    /// Returns the colour scheme with the colour of a face replaced.
+    /// Replacing ``Face::X`` changes the colour of masked stickers.
+    pub fn with_color(&self, face: Face, color: Color) -> Self {
+        let mut scheme = self.clone();
+
+        match ORDERED_FACES.iter().position(|&f| f == face) {
+            Some(i) => scheme.colors[i] = color,
+            None => scheme.masked = color,
+        }
+
+        scheme
+    }
+
+    /// The colour of the stickers on a face.
+    pub fn color(&self, face: Face) -> &Color {
+        match ORDERED_FACES.iter().position(|&f| f == face) {
+            Some(i) => &self.colors[i],
+            None => &self.masked,
+        }
+    }
+
+    /// The face whose stickers are written with the given colour letter.
+    pub fn face(&self, letter: char) -> Option<Face> {
+        let letter = letter.to_ascii_uppercase();
+
+        ORDERED_FACES
+            .iter()
+            .copied()
+            .chain(std::iter::once(Face::X))
+            .find(|&f| self.color(f).letter.to_ascii_uppercase() == letter)
+    }
+
+    /// Parses a cube state written with colour letters into a ``FaceletCube``.
+    ///
+    /// The stickers are listed in the same order as ``Cube::state``, and whitespace is
+    /// ignored. As with ``FaceletCube::from_state``, each sticker is tracked by working
+    /// out which piece it belongs to from the colours. Returns ``None`` if a letter is not
+    /// part of the colour scheme, the number of stickers does not match any cube size, or
+    /// the stickers do not make up each piece of the cube exactly once, which includes
+    /// states with masked stickers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant};
+    /// use cubesim::{FaceletCube, ColorScheme};
+    ///
+    /// let cube = ColorScheme::western().parse_state("
+    ///     WWWWWWWWW BBBRRRRRR RRRGGGGGG YYYYYYYYY GGGOOOOOO OOOBBBBBB
+    /// ").unwrap();
+    /// assert_eq!(cube.state(), FaceletCube::new(3).apply_move(Move::U(MoveVariant::Standard)).state());
+    /// ```
+    pub fn parse_state(&self, state: &str) -> Option<FaceletCube> {
+        let faces = state
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| self.face(c))
+            .collect::<Option<Vec<_>>>()?;
+
+        FaceletCube::from_state(&faces)
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::western()
+    }
+}
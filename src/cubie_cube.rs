@@ -0,0 +1,380 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::generic_cube::{Cube, Face, Move, MoveVariant, CubeSize, ORDERED_FACES};
+use crate::generic_cube::{sticker_index as S};
+use crate::generic_cube::Face::*;
+use crate::facelet_cube::FaceletCube;
+use crate::symmetry::face_move;
+
+/// The facelets of each corner position of a 3x3x3 cube, listed clockwise
+/// starting from the U or D facelet.
+///
+/// Positions are ordered URF, UFL, ULB, UBR, DFR, DLF, DBL, DRB.
+pub const CORNER_FACELETS: [[(Face, CubeSize); 3]; 8] = [
+    [(U, 9), (R, 1), (F, 3)],
+    [(U, 7), (F, 1), (L, 3)],
+    [(U, 1), (L, 1), (B, 3)],
+    [(U, 3), (B, 1), (R, 3)],
+    [(D, 3), (F, 9), (R, 7)],
+    [(D, 1), (L, 9), (F, 7)],
+    [(D, 7), (B, 9), (L, 7)],
+    [(D, 9), (R, 9), (B, 7)],
+];
+
+/// The index on a 2x2x2 face of the corner facelet with the given index on a 3x3x3
+/// face. The corners of a 2x2x2 face are the corners of a 3x3x3 face.
+pub(crate) fn corner_index_2x2(index: CubeSize) -> CubeSize {
+    [1, 0, 2, 0, 0, 0, 3, 0, 4][index as usize - 1]
+}
+
+/// The facelets of each edge position of a 3x3x3 cube, starting from the
+/// U or D facelet, or the F or B facelet for the E slice edges.
+///
+/// Positions are ordered UR, UF, UL, UB, DR, DF, DL, DB, FR, FL, BL, BR.
+pub const EDGE_FACELETS: [[(Face, CubeSize); 2]; 12] = [
+    [(U, 6), (R, 2)],
+    [(U, 8), (F, 2)],
+    [(U, 4), (L, 2)],
+    [(U, 2), (B, 2)],
+    [(D, 6), (R, 8)],
+    [(D, 2), (F, 8)],
+    [(D, 4), (L, 8)],
+    [(D, 8), (B, 8)],
+    [(F, 6), (R, 4)],
+    [(F, 4), (L, 6)],
+    [(B, 6), (L, 4)],
+    [(B, 4), (R, 6)],
+];
+
+/// A 3x3x3 cube represented by the permutation and orientation of its corners and edges.
+///
+/// ``cp[i]`` is the corner occupying corner position ``i`` and ``co[i]`` is its
+/// clockwise twist. Edges are described the same way by ``ep`` and ``eo``.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CubieCube {
+    pub cp: [u8; 8],
+    pub co: [u8; 8],
+    pub ep: [u8; 12],
+    pub eo: [u8; 12],
+}
+
+impl CubieCube {
+    /// Creates a solved cube.
+    pub fn solved() -> Self {
+        Self {
+            cp: [0, 1, 2, 3, 4, 5, 6, 7],
+            co: [0; 8],
+            ep: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+            eo: [0; 12],
+        }
+    }
+
+    /// Converts the cube into a ``FaceletCube`` whose stickers keep track of
+    /// the solved position they originated from.
+    pub fn to_facelet_cube(&self) -> FaceletCube {
+        let solved = |f: Face, i: CubeSize| (f, S(3, f, i) as u16);
+        let mut faces = (0..54)
+            .map(|i| (ORDERED_FACES[i / 9], i as u16))
+            .collect::<Vec<_>>();
+
+        for (position, facelets) in CORNER_FACELETS.iter().enumerate() {
+            let corner = CORNER_FACELETS[self.cp[position] as usize];
+            let twist = self.co[position] as usize;
+
+            for (n, &(f, i)) in corner.iter().enumerate() {
+                let (target_face, target_index) = facelets[(n + twist) % 3];
+                faces[S(3, target_face, target_index) as usize] = solved(f, i);
+            }
+        }
+
+        for (position, facelets) in EDGE_FACELETS.iter().enumerate() {
+            let edge = EDGE_FACELETS[self.ep[position] as usize];
+            let flip = self.eo[position] as usize;
+
+            for (n, &(f, i)) in edge.iter().enumerate() {
+                let (target_face, target_index) = facelets[(n + flip) % 2];
+                faces[S(3, target_face, target_index) as usize] = solved(f, i);
+            }
+        }
+
+        FaceletCube::from_tracked_faces(3, faces)
+    }
+
+    /// Reads the corners and edges of a 3x3x3 ``FaceletCube`` from the solved
+    /// positions that its stickers keep track of.
+    ///
+    /// Returns ``None`` if the cube is not a 3x3x3 in standard orientation, or if
+    /// its stickers do not make up each corner and edge exactly once.
+    pub fn from_facelet_cube(cube: &FaceletCube) -> Option<Self> {
+        if cube.size() != 3 {
+            return None;
+        }
+
+        let faces = cube.tracked_faces();
+        let mut result = Self::solved();
+
+        // Finds the piece and the sticker of it whose solved index is tracked at a facelet.
+        fn find<const N: usize>(pieces: &[[(Face, CubeSize); N]], id: u16) -> Option<(u8, u8)> {
+            pieces.iter().enumerate().find_map(|(piece, facelets)| {
+                facelets
+                    .iter()
+                    .position(|&(f, i)| S(3, f, i) as u16 == id)
+                    .map(|n| (piece as u8, n as u8))
+            })
+        }
+
+        for (position, facelets) in CORNER_FACELETS.iter().enumerate() {
+            let (f, i) = facelets[0];
+            let (corner, n) = find(&CORNER_FACELETS, faces[S(3, f, i) as usize].1)?;
+            result.cp[position] = corner;
+            result.co[position] = (3 - n) % 3;
+        }
+
+        for (position, facelets) in EDGE_FACELETS.iter().enumerate() {
+            let (f, i) = facelets[0];
+            let (edge, n) = find(&EDGE_FACELETS, faces[S(3, f, i) as usize].1)?;
+            result.ep[position] = edge;
+            result.eo[position] = n;
+        }
+
+        let is_permutation = |pieces: &[u8]| (0..pieces.len() as u8).all(|p| pieces.contains(&p));
+
+        if is_permutation(&result.cp) && is_permutation(&result.ep) {
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    /// The effect of a face turn of a 3x3x3 cube on its corners and edges.
+    ///
+    /// Panics if the move is not a face turn, such as a slice move or cube rotation.
+    pub fn from_move(mv: Move) -> Self {
+        let cube = FaceletCube::new(3).apply_move(mv);
+        assert!(cube.orientation() == Some(vec![]), "only face turns can be applied to a CubieCube");
+
+        Self::from_facelet_cube(&cube).unwrap()
+    }
+
+    /// Applies ``other`` to the cube, as if its moves were made after those of ``self``.
+    pub fn multiply(&self, other: &Self) -> Self {
+        let mut result = Self::solved();
+
+        for i in 0..8 {
+            let from = other.cp[i] as usize;
+            result.cp[i] = self.cp[from];
+            result.co[i] = (self.co[from] + other.co[i]) % 3;
+        }
+
+        for i in 0..12 {
+            let from = other.ep[i] as usize;
+            result.ep[i] = self.ep[from];
+            result.eo[i] = (self.eo[from] + other.eo[i]) % 2;
+        }
+
+        result
+    }
+
+    /// The cube which undoes this one, so that multiplying the two gives the solved cube.
+    pub fn inverse(&self) -> Self {
+        let mut result = Self::solved();
+
+        for i in 0..8 {
+            let corner = self.cp[i] as usize;
+            result.cp[corner] = i as u8;
+            result.co[corner] = (3 - self.co[i]) % 3;
+        }
+
+        for i in 0..12 {
+            let edge = self.ep[i] as usize;
+            result.ep[edge] = i as u8;
+            result.eo[edge] = self.eo[i];
+        }
+
+        result
+    }
+
+    /// Whether the cube can be solved by moves: the corner twists and edge flips add
+    /// up, and the corner and edge permutations have matching parity.
+    pub fn is_solvable(&self) -> bool {
+        self.co.iter().sum::<u8>() % 3 == 0
+            && self.eo.iter().sum::<u8>() % 2 == 0
+            && permutation_parity(&self.cp) == permutation_parity(&self.ep)
+    }
+
+    /// The twists of the first seven corners as a number in ``0..2187``. The twist
+    /// of the last corner follows from the others.
+    pub fn twist(&self) -> usize {
+        self.co[..7].iter().fold(0, |twist, &co| 3 * twist + co as usize)
+    }
+
+    /// The flips of the first eleven edges as a number in ``0..2048``. The flip
+    /// of the last edge follows from the others.
+    pub fn flip(&self) -> usize {
+        self.eo[..11].iter().fold(0, |flip, &eo| 2 * flip + eo as usize)
+    }
+}
+
+/// The rank of a permutation among all permutations of its pieces, in
+/// ``0..n!``. The identity permutation has rank 0.
+pub fn permutation_rank(pieces: &[u8]) -> usize {
+    (0..pieces.len()).fold(0, |rank, i| {
+        let smaller = pieces[i + 1..].iter().filter(|&&p| p < pieces[i]).count();
+        rank * (pieces.len() - i) + smaller
+    })
+}
+
+/// The permutation of ``n`` pieces with the given rank, the inverse of ``permutation_rank``.
+pub fn permutation_unrank(mut rank: usize, n: usize) -> Vec<u8> {
+    let mut digits = vec![0; n];
+
+    for i in (0..n).rev() {
+        digits[i] = rank % (n - i);
+        rank /= n - i;
+    }
+
+    let mut unused = (0..n as u8).collect::<Vec<_>>();
+    digits.into_iter().map(|d| unused.remove(d)).collect()
+}
+
+/// The number of face turns of a 3x3x3 cube.
+///
+/// Face turns are indexed by ``3 * face + power - 1`` with faces in the order of
+/// ``ORDERED_FACES``. The powers are clockwise, double and anticlockwise turns.
+pub const FACE_TURNS: usize = 18;
+
+/// The face turn with the given index.
+pub fn face_turn(index: usize) -> Move {
+    let variant = [MoveVariant::Standard, MoveVariant::Double, MoveVariant::Inverse][index % 3];
+    face_move(ORDERED_FACES[index / 3], None, variant)
+}
+
+/// Whether a face turn may follow another in a search. Turning the same face twice
+/// in a row is never needed, and turns of opposite faces are only made in one order.
+pub fn may_follow(previous: Option<usize>, m: usize) -> bool {
+    match previous {
+        Some(previous) => {
+            let (face, previous_face) = (m / 3, previous / 3);
+            face != previous_face && face + 3 != previous_face
+        }
+        None => true,
+    }
+}
+
+/// Builds the move table of a coordinate, for the given face turns.
+///
+/// The table gives the coordinate reached by applying each face turn to each value of
+/// the coordinate, at ``value * FACE_TURNS + turn``. Every value of the coordinate is
+/// reached by a breadth first search from the solved cube, and the cube it was first
+/// reached with stands in for all cubes with that value.
+pub fn move_table(
+    move_cubes: &[CubieCube], size: usize, moves: &[usize], coordinate: impl Fn(&CubieCube) -> usize
+) -> Vec<u16> {
+    let mut table = vec![u16::MAX; size * FACE_TURNS];
+    let mut seen = vec![false; size];
+    let mut frontier = VecDeque::from([CubieCube::solved()]);
+    seen[coordinate(&CubieCube::solved())] = true;
+
+    while let Some(cube) = frontier.pop_front() {
+        let from = coordinate(&cube);
+
+        for &m in moves {
+            let next = cube.multiply(&move_cubes[m]);
+            let to = coordinate(&next);
+            table[from * FACE_TURNS + m] = to as u16;
+
+            if !seen[to] {
+                seen[to] = true;
+                frontier.push_back(next);
+            }
+        }
+    }
+
+    table
+}
+
+/// The version of the tables saved by ``cached_table``. It must be increased whenever
+/// the layout or contents of any table change, so that old files are not loaded.
+const CACHE_VERSION: u32 = 1;
+
+/// The magic bytes, version and checksum in front of each cached table.
+const CACHE_HEADER_BYTES: usize = 16;
+
+/// Reads the table called ``name`` from ``cache_dir`` if it has the expected number of
+/// bytes and its header matches, and otherwise generates it and saves it there. An
+/// error saving the table is recorded in ``result``, and the generated table is still
+/// returned.
+///
+/// Each file starts with the current ``CACHE_VERSION`` and a checksum of the table,
+/// so that tables saved by other versions or damaged on disk are generated again.
+pub fn cached_table(
+    cache_dir: &Path, name: &str, bytes: usize, generate: &dyn Fn() -> Vec<u8>, result: &mut io::Result<()>
+) -> Vec<u8> {
+    static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0);
+
+    let path = cache_dir.join(format!("{}.bin", name));
+
+    match fs::read(&path) {
+        Ok(mut data)
+            if data.len() == CACHE_HEADER_BYTES + bytes
+                && data[..CACHE_HEADER_BYTES] == cache_header(&data[CACHE_HEADER_BYTES..]) =>
+        {
+            data.drain(..CACHE_HEADER_BYTES);
+            data
+        }
+        _ => {
+            let data = generate();
+
+            // Write to a temporary file first, so that an interrupted write never leaves
+            // a partial table behind. The name is unique to this process and call, so
+            // that processes or threads saving the same table do not clash.
+            let temporary = cache_dir.join(format!(
+                "{}.{}-{}.tmp", name, process::id(), TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed)
+            ));
+            let written = fs::File::create(&temporary)
+                .and_then(|mut file| file.write_all(&cache_header(&data)).and_then(|_| file.write_all(&data)))
+                .and_then(|_| fs::rename(&temporary, &path));
+
+            if let Err(error) = written {
+                let _ = fs::remove_file(&temporary);
+                *result = Err(error);
+            }
+
+            data
+        }
+    }
+}
+
+/// The header saved in front of a cached table: the magic bytes ``CSIM``, the
+/// ``CACHE_VERSION`` and the 64-bit FNV-1a hash of the table, in little endian.
+fn cache_header(data: &[u8]) -> [u8; CACHE_HEADER_BYTES] {
+    let checksum = data.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    let mut header = [0; CACHE_HEADER_BYTES];
+    header[..4].copy_from_slice(b"CSIM");
+    header[4..8].copy_from_slice(&CACHE_VERSION.to_le_bytes());
+    header[8..].copy_from_slice(&checksum.to_le_bytes());
+    header
+}
+
+/// The parity of a permutation, with ``true`` for odd permutations.
+pub fn permutation_parity(pieces: &[u8]) -> bool {
+    let mut inversions = 0;
+
+    for i in 0..pieces.len() {
+        for j in (i + 1)..pieces.len() {
+            if pieces[i] > pieces[j] {
+                inversions += 1;
+            }
+        }
+    }
+
+    inversions % 2 == 1
+}
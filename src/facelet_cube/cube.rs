@@ -1,5 +1,11 @@
-use crate::generic_cube::{Cube, Move, Face, CubeSize};
+use cgmath::Vector3;
+use rustc_hash::FxHashMap;
+
+use crate::generic_cube::{Cube, Move, Face, CubeSize, ORDERED_FACES};
 use crate::generic_cube::Face::*;
+use crate::pieces::{PieceType, pieces, piece_type, sticker_orbit, sticker_normal};
+
+use crate::render::terminal_net;
 
 use super::moves::{compute_permutation};
 
@@ -22,13 +28,13 @@ impl Cube for FaceletCube {
     fn new(size: CubeSize) -> Self {
         Self {
             size,
-            faces: vec![
-                repeat(U, (size * size).into()),
-                repeat(R, (size * size).into()),
-                repeat(F, (size * size).into()),
-                repeat(D, (size * size).into()),
-                repeat(L, (size * size).into()),
-                repeat(B, (size * size).into()),
+            faces: [
+                repeat(U, size * size),
+                repeat(R, size * size),
+                repeat(F, size * size),
+                repeat(D, size * size),
+                repeat(L, size * size),
+                repeat(B, size * size),
             ].concat().iter().enumerate().map(|(i, s)| (*s, i as u16)).collect()
         }
     }
@@ -58,6 +64,73 @@ impl Cube for FaceletCube {
     }
 }
 
+impl FaceletCube {
+    /// Creates a cube from stickers tagged with the index they occupy on a solved cube.
+    pub(crate) fn from_tracked_faces(size: CubeSize, faces: Vec<(Face, u16)>) -> Self {
+        Self { size, faces }
+    }
+
+    /// The stickers of the cube, each tagged with the index it occupies on a solved cube.
+    pub(crate) fn tracked_faces(&self) -> &[(Face, u16)] {
+        &self.faces
+    }
+
+    /// Creates a cube from the colours of its stickers, such as a state read from a real cube.
+    ///
+    /// Unlike ``FaceletCube::from``, every sticker is matched to the position it occupies
+    /// on a solved cube by looking at the colours of its piece. The cube can then be
+    /// masked and tracked exactly like a cube built by applying moves. Center pieces
+    /// of the same colour are identical, so they are matched in an arbitrary order.
+    ///
+    /// Returns ``None`` if the number of stickers does not fit a cube, or if the stickers
+    /// do not make up each piece exactly once, such as when a sticker is ``Face::X``.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Face::*, Move, MoveVariant};
+    /// use cubesim::{FaceletCube, parse_scramble};
+    ///
+    /// let scrambled = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F'")));
+    /// let imported = FaceletCube::from_state(&scrambled.state()).unwrap();
+    /// assert!(imported == scrambled);
+    ///
+    /// let mut broken = scrambled.state();
+    /// broken[4] = D;
+    /// assert!(FaceletCube::from_state(&broken).is_none());
+    /// ```
+    pub fn from_state(state: &[Face]) -> Option<Self> {
+        let size = ((state.len() / 6) as f64).sqrt() as CubeSize;
+
+        if size == 0 || state.len() != 6 * (size * size) as usize {
+            return None;
+        }
+
+        let mut solved_pieces: FxHashMap<PieceKey, Vec<Vec<CubeSize>>> = FxHashMap::default();
+
+        for (position, stickers) in pieces(size).into_iter().rev() {
+            let colors = stickers.iter().map(|&i| solved_color(size, i)).collect::<Vec<_>>();
+            solved_pieces.entry(piece_key(size, position, &stickers, &colors)).or_default().push(stickers);
+        }
+
+        let mut faces = vec![(X, 0); state.len()];
+
+        for (position, stickers) in pieces(size) {
+            let colors = stickers.iter().map(|&i| state[i as usize]).collect::<Vec<_>>();
+            let solved = solved_pieces.get_mut(&piece_key(size, position, &stickers, &colors))?.pop()?;
+
+            for (&index, &color) in stickers.iter().zip(&colors) {
+                let solved_index = solved.iter().find(|&&i| solved_color(size, i) == color)?;
+                faces[index as usize] = (color, *solved_index as u16);
+            }
+        }
+
+        Some(Self { size, faces })
+    }
+}
+
+/// Creates a cube from the colours of its stickers without tracking them, so masks
+/// cannot tell the stickers apart. Use ``FaceletCube::from_state`` to track them.
 impl From<Vec<Face>> for FaceletCube {
     fn from(faces: Vec<Face>) -> FaceletCube {
         FaceletCube {
@@ -67,6 +140,36 @@ impl From<Vec<Face>> for FaceletCube {
     }
 }
 
+impl std::fmt::Display for FaceletCube {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", terminal_net(self, None))
+    }
+}
+
+/// What identifies a piece from its colours: its type and orbit, its colours in
+/// order, and which way round the colours go for pieces that have a mirror image.
+type PieceKey = (PieceType, CubeSize, Vec<Face>, CubeSize);
+
+fn piece_key(size: CubeSize, position: Vector3<CubeSize>, stickers: &[CubeSize], colors: &[Face]) -> PieceKey {
+    let mut by_color = stickers.iter().zip(colors).map(|(&i, &c)| (c, i)).collect::<Vec<_>>();
+    by_color.sort_unstable();
+
+    let normals = by_color.iter().map(|&(_, i)| sticker_normal(size, i)).collect::<Vec<_>>();
+    let dot = |a: Vector3<CubeSize>, b: Vector3<CubeSize>| a.x * b.x + a.y * b.y + a.z * b.z;
+    let handedness = match normals.as_slice() {
+        [a, b, c] => dot(a.cross(*b), *c).signum(),
+        [a, b] => dot(a.cross(*b), position).signum(),
+        _ => 0,
+    };
+
+    let index = stickers[0];
+    (piece_type(size, index), sticker_orbit(size, index), by_color.into_iter().map(|(c, _)| c).collect(), handedness)
+}
+
+fn solved_color(size: CubeSize, index: CubeSize) -> Face {
+    ORDERED_FACES[(index / (size * size)) as usize]
+}
+
 fn repeat<T: Clone>(element: T, count: i32) -> Vec<T> {
-    std::iter::repeat(element).take(count as usize).collect()
+    vec![element; count as usize]
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 use std::hash::Hash;
 
+use crate::color_scheme::{Color, ColorScheme};
+
 pub type CubeSize = i32;
 
 /// A Rubik's Cube of arbitrary size.
@@ -36,6 +38,23 @@ pub trait Cube: Clone + Eq + Hash + PartialEq {
     /// ```
     fn state(&self) -> Vec<Face>;
 
+    /// The colours of the stickers of a cube under a given colour scheme,
+    /// in the same order as ``Cube::state``.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::Cube;
+    /// use cubesim::{FaceletCube, Color, ColorScheme};
+    ///
+    /// let colors = FaceletCube::new(3).state_colors(&ColorScheme::japanese());
+    /// assert_eq!(colors[0], Color::white());
+    /// assert_eq!(colors[27], Color::blue());
+    /// ```
+    fn state_colors(&self, scheme: &ColorScheme) -> Vec<Color> {
+        self.state().iter().map(|&f| scheme.color(f).clone()).collect()
+    }
+
     /// Whether a cube is solved.
     fn is_solved(&self) -> bool {
         fn all_equal<T: Clone + PartialEq>(arr: &[T]) -> bool {
@@ -56,6 +75,156 @@ pub trait Cube: Clone + Eq + Hash + PartialEq {
         is_solved
     }
 
+    /// The whole cube rotation which brings the cube into standard orientation.
+    ///
+    /// A cube is in standard orientation when the U and F centers are on the U and F
+    /// faces. Even sized cubes have no fixed centers, so the DBL corner is used instead.
+    /// Returns ``None`` if the relevant stickers are masked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant};
+    /// use cubesim::FaceletCube;
+    ///
+    /// let cube = FaceletCube::new(3).apply_move(Move::X(MoveVariant::Standard));
+    /// assert_eq!(cube.orientation(), Some(vec![Move::X(MoveVariant::Inverse)]));
+    /// ```
+    fn orientation(&self) -> Option<Vec<Move>> {
+        let size = self.size();
+        let is_standard = |state: &[Face]| if size % 2 == 1 {
+            let center = (size * size + 1) / 2;
+            state[sticker_index(size, Face::U, center) as usize] == Face::U
+                && state[sticker_index(size, Face::F, center) as usize] == Face::F
+        } else {
+            let corner = size * (size - 1) + 1;
+            state[sticker_index(size, Face::D, corner) as usize] == Face::D
+                && state[sticker_index(size, Face::B, size * size) as usize] == Face::B
+                && state[sticker_index(size, Face::L, corner) as usize] == Face::L
+        };
+
+        all_rotations()
+            .into_iter()
+            .find(|rotation| is_standard(&self.apply_moves(rotation).state()))
+    }
+
+    /// Rotates the cube into standard orientation, as given by ``Cube::orientation``.
+    ///
+    /// The cube is returned unchanged if its orientation cannot be determined.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant};
+    /// use cubesim::FaceletCube;
+    ///
+    /// let cube = FaceletCube::new(3).apply_moves(&[
+    ///     Move::R(MoveVariant::Standard),
+    ///     Move::Y(MoveVariant::Standard),
+    /// ]);
+    /// assert!(cube.normalize_orientation() == FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard)));
+    /// ```
+    fn normalize_orientation(&self) -> Self {
+        match self.orientation() {
+            Some(rotation) => self.apply_moves(&rotation),
+            None => self.clone(),
+        }
+    }
+
+    /// Whether two cubes have the same state up to a whole cube rotation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant};
+    /// use cubesim::FaceletCube;
+    ///
+    /// let cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+    /// let rotated_cube = cube.apply_move(Move::Y(MoveVariant::Standard));
+    /// assert!(cube.eq_up_to_rotation(&rotated_cube));
+    /// assert!(!cube.eq_up_to_rotation(&FaceletCube::new(3)));
+    /// ```
+    fn eq_up_to_rotation(&self, other: &Self) -> bool {
+        let other_state = other.state();
+
+        all_rotations()
+            .iter()
+            .any(|rotation| self.apply_moves(rotation).state() == other_state)
+    }
+
+    /// Whether the state of a cube matches a pattern, where ``Face::X`` in the
+    /// pattern matches any sticker. A pattern for a cube of a different size never matches.
+    ///
+    /// # Examples
+    ///
+    /// Check that the cross is solved:
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Face::*, Move, MoveVariant};
+    /// use cubesim::FaceletCube;
+    ///
+    /// let cross = vec![
+    ///     X, X, X, X, X, X, X, X, X,
+    ///     X, X, X, X, R, X, X, R, X,
+    ///     X, X, X, X, F, X, X, F, X,
+    ///     X, D, X, D, D, D, X, D, X,
+    ///     X, X, X, X, L, X, X, L, X,
+    ///     X, X, X, X, B, X, X, B, X
+    /// ];
+    ///
+    /// let cube = FaceletCube::new(3).apply_move(Move::U(MoveVariant::Standard));
+    /// assert!(cube.matches_pattern(&cross));
+    /// assert!(!cube.apply_move(Move::F(MoveVariant::Standard)).matches_pattern(&cross));
+    /// ```
+    fn matches_pattern(&self, pattern: &[Face]) -> bool {
+        pattern_matches(&self.state(), pattern)
+    }
+
+    /// Matches the state of a cube against a pattern, allowing the cube to be
+    /// transformed as given by the ``MatchMode``.
+    ///
+    /// Returns the moves which transform the cube into a state matching the pattern,
+    /// or ``None`` if there are none. If the cube already matches, no moves are returned.
+    /// A pattern for a cube of a different size never matches.
+    ///
+    /// # Examples
+    ///
+    /// Recognise a pattern after an AUF:
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant};
+    /// use cubesim::{FaceletCube, MatchMode};
+    ///
+    /// let pattern = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard)).state();
+    /// let cube = FaceletCube::new(3).apply_moves(&[Move::R(MoveVariant::Standard), Move::U(MoveVariant::Inverse)]);
+    ///
+    /// assert_eq!(cube.match_pattern(&pattern, MatchMode::Exact), None);
+    /// assert_eq!(cube.match_pattern(&pattern, MatchMode::Auf), Some(vec![Move::U(MoveVariant::Standard)]));
+    /// ```
+    fn match_pattern(&self, pattern: &[Face], mode: MatchMode) -> Option<Vec<Move>> {
+        self.match_patterns(&[pattern], mode).map(|(_, moves)| moves)
+    }
+
+    /// Matches the state of a cube against several patterns, as for ``Cube::match_pattern``.
+    ///
+    /// Returns the index of the first matching pattern together with the moves which
+    /// transform the cube to match it. Each transformed state is only computed once,
+    /// so this is faster than matching the patterns one at a time.
+    fn match_patterns(&self, patterns: &[&[Face]], mode: MatchMode) -> Option<(usize, Vec<Move>)> {
+        let transformed = mode
+            .transformations()
+            .into_iter()
+            .map(|moves| (self.apply_moves(&moves).state(), moves))
+            .collect::<Vec<_>>();
+
+        patterns.iter().enumerate().find_map(|(i, pattern)| {
+            transformed
+                .iter()
+                .find(|(state, _)| pattern_matches(state, pattern))
+                .map(|(_, moves)| (i, moves.clone()))
+        })
+    }
+
     /// Replaces each piece of the cube according to the given mapping function.
     /// This is useful for defining custom solvers by replacing certain pieces
     /// in order to reduce the search space.
@@ -155,7 +324,8 @@ use derive_more::Display;
 /// The faces follow the standard WCA notation as described in the [WCA regulations].
 ///
 /// [WCA regulations]: https://worldcubeassociation.org/regulations/#article-12-notation
-#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Face {
     /// Upper face.
     U,
@@ -277,11 +447,66 @@ impl Move {
             Move::Z(_) => Move::Z(variant),
         }
     }
+
+    /// Returns the Move that undoes this Move.
+    pub fn inverse(&self) -> Move {
+        match self.get_variant() {
+            MoveVariant::Standard => self.with_variant(MoveVariant::Inverse),
+            MoveVariant::Double => *self,
+            MoveVariant::Inverse => self.with_variant(MoveVariant::Standard),
+        }
+    }
+}
+
+/// Writes the move in WCA notation, such as ``R'``, ``Uw2``, ``3Fw`` or ``x``.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Move, MoveVariant};
+///
+/// assert_eq!(Move::R(MoveVariant::Inverse).to_string(), "R'");
+/// assert_eq!(Move::Uw(2, MoveVariant::Double).to_string(), "Uw2");
+/// assert_eq!(Move::Fw(3, MoveVariant::Standard).to_string(), "3Fw");
+/// ```
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (face, layers) = match self {
+            Move::U(_) => ("U", None),
+            Move::L(_) => ("L", None),
+            Move::F(_) => ("F", None),
+            Move::R(_) => ("R", None),
+            Move::B(_) => ("B", None),
+            Move::D(_) => ("D", None),
+            Move::Uw(n, _) => ("U", Some(*n)),
+            Move::Lw(n, _) => ("L", Some(*n)),
+            Move::Fw(n, _) => ("F", Some(*n)),
+            Move::Rw(n, _) => ("R", Some(*n)),
+            Move::Bw(n, _) => ("B", Some(*n)),
+            Move::Dw(n, _) => ("D", Some(*n)),
+            Move::X(_) => ("x", None),
+            Move::Y(_) => ("y", None),
+            Move::Z(_) => ("z", None),
+        };
+
+        let suffix = match self.get_variant() {
+            MoveVariant::Standard => "",
+            MoveVariant::Double => "2",
+            MoveVariant::Inverse => "'",
+        };
+
+        match layers {
+            None => write!(f, "{}{}", face, suffix),
+            Some(2) => write!(f, "{}w{}", face, suffix),
+            Some(n) => write!(f, "{}{}w{}", n, face, suffix),
+        }
+    }
 }
 
 /// A move variation that must be applied to the ```Move``` struct.
 #[allow(dead_code)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MoveVariant {
     /// A 90 degree clockwise turn.
     Standard = 1,
@@ -291,6 +516,48 @@ pub enum MoveVariant {
     Inverse,
 }
 
+/// The transformations allowed when matching a cube against a pattern.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MatchMode {
+    /// The cube must match the pattern as it is.
+    Exact,
+    /// The cube may be adjusted by a turn of the U layer.
+    Auf,
+    /// The cube may be rotated as a whole.
+    Rotation,
+    /// The cube may be rotated as a whole and then adjusted by a turn of the U layer.
+    RotationAndAuf,
+}
+
+impl MatchMode {
+    /// The move sequences for every allowed transformation, starting with the identity.
+    fn transformations(self) -> Vec<Vec<Move>> {
+        let aufs = match self {
+            MatchMode::Exact | MatchMode::Rotation => vec![vec![]],
+            MatchMode::Auf | MatchMode::RotationAndAuf => vec![
+                vec![],
+                vec![Move::U(MoveVariant::Standard)],
+                vec![Move::U(MoveVariant::Double)],
+                vec![Move::U(MoveVariant::Inverse)],
+            ],
+        };
+
+        let rotations = match self {
+            MatchMode::Exact | MatchMode::Auf => vec![vec![]],
+            MatchMode::Rotation | MatchMode::RotationAndAuf => all_rotations(),
+        };
+
+        rotations
+            .iter()
+            .flat_map(|rotation| aufs.iter().map(move |auf| [rotation.clone(), auf.clone()].concat()))
+            .collect()
+    }
+}
+
+fn pattern_matches(state: &[Face], pattern: &[Face]) -> bool {
+    state.len() == pattern.len() && state.iter().zip(pattern).all(|(&s, &p)| p == Face::X || s == p)
+}
+
 /// Get the solved state for a cube of a given size.
 pub fn solved_state(size: CubeSize) -> Vec<Face> {
     ORDERED_FACES
@@ -322,3 +589,22 @@ pub fn all_moves(size: CubeSize) -> Vec<Move> {
 
     moveset
 }
+
+
+/// Get all 24 whole cube rotations, each as a sequence of x, y and z moves.
+///
+/// The first rotation is the identity rotation.
+pub fn all_rotations() -> Vec<Vec<Move>> {
+    use Move::*;
+    use MoveVariant::*;
+
+    let mut rotations = Vec::new();
+
+    for top in [vec![], vec![Z(Standard)], vec![Z(Inverse)], vec![X(Standard)], vec![X(Inverse)], vec![X(Double)]] {
+        for spin in [vec![], vec![Y(Standard)], vec![Y(Double)], vec![Y(Inverse)]] {
+            rotations.push([top.clone(), spin].concat());
+        }
+    }
+
+    rotations
+}
@@ -38,8 +38,8 @@ pub struct Solver {
 /// that would take too many moves to solve, we can abandon this search branch, greatly reducing
 /// our search space.
 pub struct PruningTable {
-    pruning_table: FxHashMap<Vec<Face>, i32>,
-    depth: i32 
+    pub(crate) pruning_table: FxHashMap<Vec<Face>, i32>,
+    pub(crate) depth: i32 
 }
 
 impl PruningTable {
@@ -0,0 +1,232 @@
+use cgmath::{Basis3, InnerSpace, Rotation, Vector3};
+
+use crate::generic_cube::{Cube, Face, Move};
+
+use super::cube::GeoCube;
+use super::moves::GeometricMove;
+use super::sticker::Sticker;
+
+/// The rate at which a move is turned over the course of its animation.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Easing {
+    /// The move is turned at a constant speed.
+    Linear,
+    /// The move starts slowly and speeds up.
+    EaseIn,
+    /// The move starts quickly and slows down.
+    EaseOut,
+    /// The move speeds up and then slows down, like a move on a physical cube.
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps the fraction of an animation that has elapsed to the fraction of the move
+    /// that has been turned. Values outside of ``[0, 1]`` are clamped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::Easing;
+    ///
+    /// assert_eq!(Easing::Linear.apply(0.25), 0.25);
+    /// assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    /// assert_eq!(Easing::EaseIn.apply(1.5), 1.0);
+    /// ```
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0 },
+        }
+    }
+}
+
+/// The position and orientation of a sticker, which may be part way through a move.
+///
+/// Coordinates are those of the ``GeoCube``, so the cube spans ``[-size, size]``
+/// along each axis and each sticker is two units wide.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StickerTransform {
+    /// The face the sticker belongs to when solved, which determines its colour.
+    pub face: Face,
+    /// The center of the sticker.
+    pub center: Vector3<f64>,
+    /// The unit vector pointing out of the front of the sticker.
+    pub normal: Vector3<f64>,
+    /// A unit vector parallel to one of the edges of the sticker.
+    pub tangent: Vector3<f64>,
+    /// Whether the sticker is turned by the move being animated.
+    pub turning: bool,
+}
+
+impl StickerTransform {
+    fn new(sticker: &Sticker) -> Self {
+        let position = [sticker.current.x, sticker.current.y, sticker.current.z];
+        let axis = position.iter().position(|c| c.abs() == sticker.size).unwrap();
+
+        let mut normal = [0.0; 3];
+        normal[axis] = position[axis].signum() as f64;
+        let mut tangent = [0.0; 3];
+        tangent[(axis + 1) % 3] = 1.0;
+
+        Self {
+            face: sticker.initial_face(),
+            center: Vector3::new(position[0] as f64, position[1] as f64, position[2] as f64),
+            normal: Vector3::from(normal),
+            tangent: Vector3::from(tangent),
+            turning: false,
+        }
+    }
+
+    fn turn(&self, rotation: &Basis3<f64>) -> Self {
+        Self {
+            center: rotation.rotate_vector(self.center),
+            normal: rotation.rotate_vector(self.normal),
+            tangent: rotation.rotate_vector(self.tangent),
+            turning: true,
+            ..*self
+        }
+    }
+
+    /// The corners of the sticker, shrunk towards its center by an inset given as a
+    /// fraction of its width. The corners are listed counter-clockwise when viewed
+    /// from the front of the sticker.
+    pub fn corners(&self, inset: f64) -> [Vector3<f64>; 4] {
+        let half_width = 1.0 - 2.0 * inset;
+        let tangent = self.tangent * half_width;
+        let bitangent = self.normal.cross(self.tangent).normalize() * half_width;
+
+        [
+            self.center - tangent - bitangent,
+            self.center + tangent - bitangent,
+            self.center + tangent + bitangent,
+            self.center - tangent + bitangent,
+        ]
+    }
+}
+
+impl GeoCube {
+    /// The transforms of the stickers at rest, in the same order as ``stickers``.
+    pub fn sticker_transforms(&self) -> Vec<StickerTransform> {
+        self.stickers.iter().map(|(s, _)| StickerTransform::new(s)).collect()
+    }
+
+    /// The transforms of the stickers part way through a move, in the same order as ``stickers``.
+    ///
+    /// ``t`` is the fraction of the animation that has elapsed, which is mapped to the
+    /// fraction of the move that has been turned by the easing. At ``t = 0`` the stickers
+    /// are at rest on this cube, and at ``t = 1`` they are at rest on the turned cube.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant};
+    /// use cubesim::{GeoCube, Easing};
+    ///
+    /// let cube = GeoCube::new(3);
+    /// let halfway = cube.animate_move(Move::R(MoveVariant::Standard), 0.5, Easing::Linear);
+    /// let tilted = halfway.iter().filter(|s| s.normal.y.abs() > 0.1 && s.normal.z.abs() > 0.1).count();
+    /// assert_eq!(tilted, 4 * 3);
+    /// ```
+    pub fn animate_move(&self, mv: Move, t: f64, easing: Easing) -> Vec<StickerTransform> {
+        let geometric_move = GeometricMove::from(mv);
+        let rotation = geometric_move.get_partial_rotation_matrix(easing.apply(t));
+
+        self.stickers
+            .iter()
+            .map(|(s, _)| {
+                let transform = StickerTransform::new(s);
+                if (geometric_move.predicate)(s) { transform.turn(&rotation) } else { transform }
+            })
+            .collect()
+    }
+}
+
+/// A sequence of moves played one after another on a cube, for animating algorithms and solutions.
+///
+/// Every move takes the same amount of time, including double moves.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{GeoCube, Timeline, parse_scramble};
+///
+/// let moves = parse_scramble(String::from("R U R' U'"));
+/// let mut timeline = Timeline::new(&GeoCube::new(3), &moves);
+/// timeline.move_duration = 0.5;
+///
+/// assert_eq!(timeline.duration(), 2.0);
+/// assert_eq!(timeline.position(1.25), Some((2, 0.5)));
+/// assert_eq!(timeline.frames(10.0).count(), 21);
+/// ```
+#[derive(Clone)]
+pub struct Timeline {
+    /// The easing applied to each move.
+    pub easing: Easing,
+    /// The time taken by each move.
+    pub move_duration: f64,
+    moves: Vec<Move>,
+    states: Vec<GeoCube>,
+}
+
+impl Timeline {
+    /// Creates a timeline playing the moves on the cube, with each move taking one unit of time.
+    pub fn new(cube: &GeoCube, moves: &[Move]) -> Self {
+        let mut states = vec![cube.clone()];
+
+        for &mv in moves {
+            states.push(states.last().unwrap().apply_move(mv));
+        }
+
+        Self { easing: Easing::default(), move_duration: 1.0, moves: moves.to_vec(), states }
+    }
+
+    /// The moves played by the timeline.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// The cube before each move, followed by the cube after the last move.
+    pub fn states(&self) -> &[GeoCube] {
+        &self.states
+    }
+
+    /// The total time taken to play every move.
+    pub fn duration(&self) -> f64 {
+        self.moves.len() as f64 * self.move_duration
+    }
+
+    /// The index of the move being played at a time, and the fraction of its animation
+    /// that has elapsed. Returns ``None`` before the start and after the end of the timeline.
+    pub fn position(&self, time: f64) -> Option<(usize, f64)> {
+        if time < 0.0 || time >= self.duration() {
+            return None;
+        }
+
+        let index = ((time / self.move_duration) as usize).min(self.moves.len() - 1);
+        Some((index, time / self.move_duration - index as f64))
+    }
+
+    /// The transforms of the stickers at a time. Before the start of the timeline the
+    /// stickers are at rest on the initial cube, and after the end on the final cube.
+    pub fn frame(&self, time: f64) -> Vec<StickerTransform> {
+        match self.position(time) {
+            Some((index, t)) => self.states[index].animate_move(self.moves[index], t, self.easing),
+            None if time < 0.0 => self.states[0].sticker_transforms(),
+            None => self.states.last().unwrap().sticker_transforms(),
+        }
+    }
+
+    /// The frames of the whole timeline sampled at a frame rate, given in frames per unit
+    /// of time. The first frame is at the start and the last frame is at the end.
+    pub fn frames(&self, frame_rate: f64) -> impl Iterator<Item = Vec<StickerTransform>> + '_ {
+        let count = (self.duration() * frame_rate + 1e-9).floor() as usize + 1;
+
+        (0..count).map(move |k| self.frame(k as f64 / frame_rate))
+    }
+}
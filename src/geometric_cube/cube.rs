@@ -55,24 +55,19 @@ impl Cube for GeoCube {
     }
 
     fn state(&self) -> Vec<Face> {
-        let mut faces = Vec::new();
+        let mut faces = vec![Face::X; self.stickers.len()];
 
-        for mvs in FACE_ROTATING_MOVES.iter() {
-            let rotated_cube = self.apply_moves(&mvs);
-            let top_layer_stickers = rotated_cube.top_layer_stickers();
-            
-            for (sticker, _) in top_layer_stickers {
-                faces.push(sticker.initial_face());
-            }
+        for (sticker, _) in &self.stickers {
+            faces[sticker.current_index() as usize] = sticker.initial_face();
         }
-        
+
         faces
     }
 
     fn mask(&self, mask: &dyn Fn(CubeSize, Face) -> Face) -> Self {
         let masked_stickers = self.stickers
                                   .iter()
-                                  .map(|(s, i)| (Sticker { face: mask(*i, s.initial_face()), ..*s }, *i))
+                                  .map(|(s, i)| (Sticker { face: mask(s.set_solved().current_index(), s.initial_face()), ..*s }, *i))
                                   .collect::<Vec<_>>();
 
         Self { stickers: masked_stickers, ..*self }
@@ -101,7 +96,7 @@ impl GeoCube {
         let cube = Self { size, stickers };
 
         for (idx, mvs) in (FACE_ROTATING_MOVES).iter().enumerate() {
-            let rotated_cube = cube.apply_moves(&mvs);
+            let rotated_cube = cube.apply_moves(mvs);
             let top_layer_stickers = rotated_cube.top_layer_stickers();
 
             for (sticker, _) in top_layer_stickers.iter() {
@@ -114,8 +109,8 @@ impl GeoCube {
 
     fn top_layer_stickers(&self) -> Vec<(Sticker, CubeSize)> {
         let mut top_layer_stickers = self.stickers
-            .to_owned()
-            .into_iter()
+            .iter()
+            .copied()
             .filter(|(s, _)| matches!(s.current_face(), Face::U))
             .collect::<Vec<_>>();
 
@@ -1,7 +1,11 @@
+pub mod animation;
 pub mod cube;
 pub mod moves;
+pub mod picking;
 
+pub use animation::{Easing, StickerTransform, Timeline};
 pub use cube::GeoCube;
+pub use picking::StickerHit;
 pub use sticker::Sticker;
 
 mod sticker;
\ No newline at end of file
@@ -14,10 +14,17 @@ pub struct GeometricMove {
 
 impl GeometricMove {
     pub fn get_rotation_matrix(&self) -> Basis3<f64> {
+        self.get_partial_rotation_matrix(1.0)
+    }
+
+    /// The rotation by the given fraction of the move's full angle.
+    pub fn get_partial_rotation_matrix(&self, fraction: f64) -> Basis3<f64> {
+        let angle = Deg(-self.angle * fraction);
+
         match self.axis {
-            Axis::X => Basis3::from_angle_x(Deg(-self.angle)),
-            Axis::Y => Basis3::from_angle_y(Deg(-self.angle)),
-            Axis::Z => Basis3::from_angle_z(Deg(-self.angle))
+            Axis::X => Basis3::from_angle_x(angle),
+            Axis::Y => Basis3::from_angle_y(angle),
+            Axis::Z => Basis3::from_angle_z(angle)
         }
     }
 
@@ -0,0 +1,173 @@
+use cgmath::{InnerSpace, Vector3};
+
+use crate::generic_cube::{Face, Move, MoveVariant, CubeSize};
+
+use super::cube::GeoCube;
+use super::sticker::Sticker;
+
+/// A sticker hit by a ray.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StickerHit {
+    /// The face of the cube which was hit.
+    pub face: Face,
+    /// The index of the facelet which was hit, following the ordering of ``Cube::state``.
+    pub index: CubeSize,
+    /// The point at which the ray enters the cube.
+    pub point: Vector3<f64>,
+    /// The distance from the origin of the ray to the hit, in multiples of its direction.
+    pub distance: f64,
+}
+
+impl GeoCube {
+    /// Finds the sticker hit by a ray.
+    ///
+    /// The ray is given in the coordinates of the ``GeoCube``, so the cube spans
+    /// ``[-size, size]`` along each axis with the x, y and z axes pointing towards the
+    /// R, U and F faces. Returns ``None`` if the ray misses the cube or starts inside it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cgmath::Vector3;
+    /// use cubesim::prelude::{Cube, Face};
+    /// use cubesim::{GeoCube, sticker_index};
+    ///
+    /// let cube = GeoCube::new(3);
+    /// let hit = cube.pick(Vector3::new(2.0, 2.0, 10.0), Vector3::new(0.0, 0.0, -1.0)).unwrap();
+    /// assert_eq!(hit.face, Face::F);
+    /// assert_eq!(hit.index, sticker_index(3, Face::F, 3));
+    /// assert_eq!(hit.distance, 7.0);
+    /// ```
+    pub fn pick(&self, origin: Vector3<f64>, direction: Vector3<f64>) -> Option<StickerHit> {
+        let n = self.size as f64;
+        let (mut near, mut far) = (f64::NEG_INFINITY, f64::INFINITY);
+        let mut hit_axis = None;
+
+        for axis in 0..3 {
+            if direction[axis] == 0.0 {
+                if origin[axis].abs() > n {
+                    return None;
+                }
+                continue;
+            }
+
+            let t1 = (-n - origin[axis]) / direction[axis];
+            let t2 = (n - origin[axis]) / direction[axis];
+
+            if t1.min(t2) > near {
+                near = t1.min(t2);
+                hit_axis = Some(axis);
+            }
+            far = far.min(t1.max(t2));
+        }
+
+        let axis = hit_axis?;
+
+        if near > far || near < 0.0 {
+            return None;
+        }
+
+        let mut point = origin + direction * near;
+        point[axis] = n * point[axis].signum();
+
+        let center = |v: f64| {
+            let offset = ((v + n) / 2.0).floor() as CubeSize;
+            2 * offset.clamp(0, self.size - 1) - self.size + 1
+        };
+
+        let mut position = [center(point.x), center(point.y), center(point.z)];
+        position[axis] = self.size * point[axis].signum() as CubeSize;
+
+        let sticker = Sticker::new(self.size, position[0], position[1], position[2]);
+
+        Some(StickerHit { face: sticker.current_face(), index: sticker.current_index(), point, distance: near })
+    }
+
+    /// Finds the moves which turn the layer containing a facelet in the direction of a drag.
+    ///
+    /// The facelet is given by its index, following the ordering of ``Cube::state``, and
+    /// the drag direction is given in the coordinates used by ``pick``. The direction is
+    /// snapped to the nearest edge of the facelet, so only the rough direction matters.
+    ///
+    /// Outer layers are turned by a single face move. If ``wide`` is set, every layer from
+    /// the nearest face up to the facelet's layer is turned with a single wide move.
+    /// Otherwise an inner layer is turned on its own, which takes a wide move followed by
+    /// the opposite wide move one layer thinner, as there are no slice moves.
+    ///
+    /// Returns ``None`` if the direction does not run along the face of the facelet, or
+    /// if there is no facelet with the given index.
+    ///
+    /// # Examples
+    ///
+    /// Dragging the top right sticker of the F face upwards:
+    ///
+    /// ```rust
+    /// use cgmath::Vector3;
+    /// use cubesim::prelude::{Cube, Face, Move, MoveVariant};
+    /// use cubesim::{GeoCube, sticker_index};
+    ///
+    /// let cube = GeoCube::new(3);
+    /// let moves = cube.resolve_drag(sticker_index(3, Face::F, 3), Vector3::new(0.1, 1.0, 0.0), false);
+    /// assert_eq!(moves, Some(vec![Move::R(MoveVariant::Standard)]));
+    /// ```
+    pub fn resolve_drag(&self, index: CubeSize, direction: Vector3<f64>, wide: bool) -> Option<Vec<Move>> {
+        if !(0..6 * self.size * self.size).contains(&index) {
+            return None;
+        }
+
+        let position = Sticker::index_position(self.size, index);
+        let position = [position.x, position.y, position.z];
+
+        let normal_axis = position.iter().position(|c| c.abs() == self.size).unwrap();
+        let mut normal = Vector3::new(0.0, 0.0, 0.0);
+        normal[normal_axis] = position[normal_axis].signum() as f64;
+
+        let along_face = direction - normal * direction.dot(normal);
+        let drag_axis = (0..3)
+            .filter(|&a| a != normal_axis)
+            .max_by(|&a, &b| along_face[a].abs().total_cmp(&along_face[b].abs()))
+            .unwrap();
+
+        if along_face[drag_axis].abs() < 1e-9 {
+            return None;
+        }
+
+        let mut drag = Vector3::new(0.0, 0.0, 0.0);
+        drag[drag_axis] = along_face[drag_axis].signum();
+
+        // Turning a quarter turn anticlockwise about this axis moves the facelet along the drag.
+        let turn_axis = normal.cross(drag);
+        let axis = 3 - normal_axis - drag_axis;
+
+        let layer = position[axis];
+        let positive = layer > 0;
+        let depth = if positive { (self.size - layer + 1) / 2 } else { (self.size + layer + 1) / 2 };
+
+        // A clockwise face move turns clockwise when looking at the face from outside.
+        let clockwise = (turn_axis[axis] > 0.0) != positive;
+        let variant = if clockwise { MoveVariant::Standard } else { MoveVariant::Inverse };
+
+        let mv = layer_move(axis, positive, depth, variant);
+
+        if wide || depth == 1 {
+            Some(vec![mv])
+        } else {
+            Some(vec![mv, layer_move(axis, positive, depth - 1, variant).inverse()])
+        }
+    }
+}
+
+/// The move turning the given number of layers from the face on one side of an axis.
+fn layer_move(axis: usize, positive: bool, depth: CubeSize, variant: MoveVariant) -> Move {
+    let outer = depth == 1;
+
+    match (axis, positive) {
+        (0, true) => if outer { Move::R(variant) } else { Move::Rw(depth, variant) },
+        (0, false) => if outer { Move::L(variant) } else { Move::Lw(depth, variant) },
+        (1, true) => if outer { Move::U(variant) } else { Move::Uw(depth, variant) },
+        (1, false) => if outer { Move::D(variant) } else { Move::Dw(depth, variant) },
+        (2, true) => if outer { Move::F(variant) } else { Move::Fw(depth, variant) },
+        (2, false) => if outer { Move::B(variant) } else { Move::Bw(depth, variant) },
+        _ => unreachable!(),
+    }
+}
@@ -1,7 +1,7 @@
 use derive_more::Display;
 use cgmath::{Rotation, Vector3};
 
-use crate::generic_cube::{Face, CubeSize}; 
+use crate::generic_cube::{Face, CubeSize, ORDERED_FACES, sticker_index};
 use crate::geometric_cube::moves::{GeometricMove};
 
 #[derive(Copy, Clone, Display, Eq, Hash, PartialEq)]
@@ -31,6 +31,44 @@ impl Sticker {
         self.face
     }
 
+    /// The index of the facelet this sticker currently occupies, following
+    /// the ordering of ``Cube::state``.
+    pub fn current_index(&self) -> CubeSize {
+        let offset = |v: CubeSize| (v + self.size - 1) / 2;
+        let Vector3 { x, y, z } = self.current;
+
+        let (face, row, col) = match self.current_face() {
+            Face::U => (Face::U, offset(z), offset(x)),
+            Face::R => (Face::R, offset(-y), offset(-z)),
+            Face::F => (Face::F, offset(-y), offset(x)),
+            Face::D => (Face::D, offset(-z), offset(x)),
+            Face::L => (Face::L, offset(-y), offset(z)),
+            Face::B => (Face::B, offset(-y), offset(-x)),
+            Face::X => unreachable!("stickers always lie on the surface of the cube"),
+        };
+
+        sticker_index(self.size, face, row * self.size + col + 1)
+    }
+
+    /// The position of the facelet with the given index, following the ordering
+    /// of ``Cube::state``. This is the inverse of ``current_index``.
+    pub fn index_position(size: CubeSize, index: CubeSize) -> Vector3<CubeSize> {
+        let face = ORDERED_FACES[(index / (size * size)) as usize];
+        let row = index % (size * size) / size;
+        let col = index % size;
+        let coordinate = |offset: CubeSize| 2 * offset - size + 1;
+
+        match face {
+            Face::U => Vector3::new(coordinate(col), size, coordinate(row)),
+            Face::R => Vector3::new(size, -coordinate(row), -coordinate(col)),
+            Face::F => Vector3::new(coordinate(col), -coordinate(row), size),
+            Face::D => Vector3::new(coordinate(col), -size, -coordinate(row)),
+            Face::L => Vector3::new(-size, -coordinate(row), coordinate(col)),
+            Face::B => Vector3::new(-coordinate(col), -coordinate(row), -size),
+            Face::X => unreachable!(),
+        }
+    }
+
     pub fn rotate(&self, mv: GeometricMove) -> Self {
         if !(mv.predicate)(self) {
             return *self;
@@ -0,0 +1,255 @@
+use crate::generic_cube::{Cube, Face, CubeSize, ORDERED_FACES, all_rotations};
+use crate::generic_cube::{sticker_index as S};
+use crate::facelet_cube::FaceletCube;
+use crate::cubie_cube::{CORNER_FACELETS, EDGE_FACELETS, corner_index_2x2, permutation_parity};
+
+/// The result of filling in the unknown stickers of a cube state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Completion {
+    /// Exactly one solvable cube matches the known stickers.
+    Unique(Vec<Face>),
+    /// Several solvable cubes match the known stickers. Only the requested
+    /// number of candidates are listed, so there may be more.
+    Ambiguous(Vec<Vec<Face>>),
+    /// No solvable cube matches the known stickers.
+    Invalid,
+}
+
+/// Fills in the unknown stickers of a 2x2x2 or 3x3x3 state, given as ``Face::X``.
+///
+/// A completed state must be solvable: each piece appears exactly once with its colours
+/// the right way round, the corner twists and edge flips add up, and the corner and
+/// edge permutations have matching parity. On the 3x3x3 the centers may be in any
+/// orientation, and unknown centers are filled in as well.
+///
+/// At most ``max_candidates`` states are listed when the completion is ambiguous.
+/// A state without unknown stickers is checked for solvability, so this also
+/// doubles as a validity check for scanned cubes. States of any other size are ``Invalid``.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face};
+/// use cubesim::{FaceletCube, Completion, complete_state, parse_scramble};
+///
+/// let state = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F'"))).state();
+///
+/// // A corner sticker can be worked out from the other two stickers of its corner.
+/// let mut scanned = state.clone();
+/// scanned[0] = Face::X;
+/// assert_eq!(complete_state(&scanned, 10), Completion::Unique(state.clone()));
+///
+/// // Two corners can be twisted in opposite directions if none of their stickers are known.
+/// let mut scanned = state.clone();
+/// for i in [0, 36, 47, 2, 45, 11] {
+///     scanned[i] = Face::X;
+/// }
+/// assert!(matches!(complete_state(&scanned, 10), Completion::Ambiguous(_)));
+/// ```
+pub fn complete_state(state: &[Face], max_candidates: usize) -> Completion {
+    let size = ((state.len() / 6) as f64).sqrt() as CubeSize;
+
+    if !(size == 2 || size == 3) || state.len() != 6 * (size * size) as usize {
+        return Completion::Invalid;
+    }
+
+    let limit = max_candidates.max(2);
+    let mut candidates = vec![];
+
+    for centers in center_orientations(size, state) {
+        // Recolour the stickers so that the centers are in standard orientation,
+        // which lets the pieces be checked against the solved cube.
+        let recolor = |f: Face| match f {
+            Face::X => Face::X,
+            f => ORDERED_FACES[centers.iter().position(|&c| c == f).unwrap()],
+        };
+        let restore = |f: Face| centers[ORDERED_FACES.iter().position(|&c| c == f).unwrap()];
+
+        let mut search = Search::new(size, state.iter().map(|&f| recolor(f)).collect(), limit - candidates.len());
+        search.place(0);
+
+        for mut found in search.found {
+            found.iter_mut().for_each(|face| *face = restore(*face));
+            candidates.push(found);
+        }
+
+        if candidates.len() >= limit {
+            break;
+        }
+    }
+
+    match candidates.len() {
+        0 => Completion::Invalid,
+        1 => Completion::Unique(candidates.remove(0)),
+        _ => {
+            candidates.truncate(max_candidates.max(1));
+            Completion::Ambiguous(candidates)
+        }
+    }
+}
+
+/// The colours of the centers in each whole cube rotation matching the known centers,
+/// in the order of ``ORDERED_FACES``. The 2x2x2 has no centers, so only the standard
+/// orientation is used.
+fn center_orientations(size: CubeSize, state: &[Face]) -> Vec<[Face; 6]> {
+    if size == 2 {
+        return vec![ORDERED_FACES];
+    }
+
+    all_rotations()
+        .iter()
+        .map(|rotation| {
+            let centers = FaceletCube::new(3).apply_moves(rotation).state();
+            [0, 1, 2, 3, 4, 5].map(|f| centers[f * 9 + 4])
+        })
+        .filter(|centers| (0..6).all(|f| state[f * 9 + 4] == Face::X || state[f * 9 + 4] == centers[f]))
+        .collect()
+}
+
+/// The stickers of each corner or edge position, as the face and index on a 3x3x3 face.
+type Facelets = &'static [&'static [(Face, CubeSize)]];
+
+const CORNERS: Facelets = &[
+    &CORNER_FACELETS[0], &CORNER_FACELETS[1], &CORNER_FACELETS[2], &CORNER_FACELETS[3],
+    &CORNER_FACELETS[4], &CORNER_FACELETS[5], &CORNER_FACELETS[6], &CORNER_FACELETS[7],
+];
+
+const EDGES: Facelets = &[
+    &EDGE_FACELETS[0], &EDGE_FACELETS[1], &EDGE_FACELETS[2], &EDGE_FACELETS[3],
+    &EDGE_FACELETS[4], &EDGE_FACELETS[5], &EDGE_FACELETS[6], &EDGE_FACELETS[7],
+    &EDGE_FACELETS[8], &EDGE_FACELETS[9], &EDGE_FACELETS[10], &EDGE_FACELETS[11],
+];
+
+/// A backtracking search placing a piece and its orientation in every corner and edge position.
+///
+/// Positions with the fewest fitting pieces are filled first, so that contradictions
+/// between the known stickers are found before trying out the unknown ones. The twist
+/// or flip of each kind of piece is checked as soon as all of its positions are filled.
+struct Search {
+    size: CubeSize,
+    state: Vec<Face>,
+    /// The positions to fill, in order, given by the piece table and position in it.
+    order: Vec<(Facelets, usize)>,
+    /// The piece and orientation in each corner position and then each edge position.
+    placed: Vec<Option<(u8, usize)>>,
+    limit: usize,
+    found: Vec<Vec<Face>>,
+}
+
+impl Search {
+    fn new(size: CubeSize, state: Vec<Face>, limit: usize) -> Self {
+        let tables = if size == 2 { vec![CORNERS] } else { vec![CORNERS, EDGES] };
+
+        let mut search = Self { size, state, order: vec![], placed: vec![None; 20], limit, found: vec![] };
+
+        let mut order = tables
+            .into_iter()
+            .flat_map(|table| (0..table.len()).map(move |position| (table, position)))
+            .collect::<Vec<_>>();
+        order.sort_by_cached_key(|&(table, position)| search.candidates(table, position).len());
+
+        search.order = order;
+        search
+    }
+
+    fn place(&mut self, depth: usize) {
+        if depth == self.order.len() {
+            self.found.push(self.completed());
+            return;
+        }
+
+        let (table, position) = self.order[depth];
+
+        for (piece, orientation) in self.candidates(table, position) {
+            self.placed[slot(table, position)] = Some((piece, orientation));
+
+            if self.is_consistent(table) {
+                self.place(depth + 1);
+            }
+
+            self.placed[slot(table, position)] = None;
+
+            if self.found.len() >= self.limit {
+                return;
+            }
+        }
+    }
+
+    /// The pieces, with their orientations, which fit the known stickers of a position
+    /// and have not been placed yet.
+    fn candidates(&self, table: Facelets, position: usize) -> Vec<(u8, usize)> {
+        let facelets = table[position];
+        let offset = slot(table, 0);
+        let used = |piece: u8| self.placed[offset..offset + table.len()].iter().any(|p| matches!(p, Some((q, _)) if *q == piece));
+
+        (0..table.len() as u8)
+            .filter(|&piece| !used(piece))
+            .flat_map(|piece| (0..facelets.len()).map(move |orientation| (piece, orientation)))
+            .filter(|&(piece, orientation)| (0..facelets.len()).all(|n| {
+                let (face, index) = facelets[(n + orientation) % facelets.len()];
+                let known = self.state[self.index(face, index)];
+                known == Face::X || known == table[piece as usize][n].0
+            }))
+            .collect()
+    }
+
+    /// Whether the placed pieces can still make up a cube which can be solved, after a
+    /// piece has been placed in the given table. The orientations of the pieces in the
+    /// table are checked once it is full, and the permutation parities once both are.
+    fn is_consistent(&self, table: Facelets) -> bool {
+        let offset = slot(table, 0);
+        let placed = &self.placed[offset..offset + table.len()];
+
+        if placed.iter().any(Option::is_none) {
+            return true;
+        }
+
+        if placed.iter().map(|p| p.unwrap().1).sum::<usize>() % table[0].len() != 0 {
+            return false;
+        }
+
+        if self.size == 2 || self.placed.iter().any(Option::is_none) {
+            return true;
+        }
+
+        let (corners, edges) = self.placed.split_at(8);
+        let pieces = |placed: &[Option<(u8, usize)>]| placed.iter().map(|p| p.unwrap().0).collect::<Vec<_>>();
+
+        permutation_parity(&pieces(corners)) == permutation_parity(&pieces(edges))
+    }
+
+    /// The position in the state of a sticker given by its index on a 3x3x3 face.
+    fn index(&self, face: Face, index: CubeSize) -> usize {
+        match self.size {
+            2 => S(2, face, corner_index_2x2(index)) as usize,
+            _ => S(3, face, index) as usize,
+        }
+    }
+
+    fn completed(&self) -> Vec<Face> {
+        let mut state = self.state.clone();
+
+        if self.size == 3 {
+            for (f, &face) in ORDERED_FACES.iter().enumerate() {
+                state[f * 9 + 4] = face;
+            }
+        }
+
+        for &(table, position) in &self.order {
+            let (piece, orientation) = self.placed[slot(table, position)].unwrap();
+            let facelets = table[position];
+
+            for n in 0..facelets.len() {
+                let (face, index) = facelets[(n + orientation) % facelets.len()];
+                state[self.index(face, index)] = table[piece as usize][n].0;
+            }
+        }
+
+        state
+    }
+}
+
+/// The index of a position in the list of placed pieces.
+fn slot(table: Facelets, position: usize) -> usize {
+    if table.len() == 8 { position } else { 8 + position }
+}
@@ -0,0 +1,416 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::generic_cube::{Cube, Move};
+use crate::facelet_cube::FaceletCube;
+use crate::cubie_cube::{CubieCube, FACE_TURNS, cached_table, face_turn, may_follow, move_table, permutation_rank};
+
+/// Options controlling how long the two-phase solver searches for.
+///
+/// The solver returns the first solution of at most ``target_length`` moves. Until
+/// then, it keeps looking for solutions shorter than the best one found so far, and
+/// gives up once ``time_limit`` has passed or no solution of at most ``max_length``
+/// moves is left to find. The best solution found is then returned, if any.
+///
+/// # Examples
+///
+/// Search for up to a tenth of a second for a solution of at most 18 moves:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use cubesim::TwoPhaseOptions;
+///
+/// let options = TwoPhaseOptions {
+///     target_length: 18,
+///     time_limit: Some(Duration::from_millis(100)),
+///     ..TwoPhaseOptions::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TwoPhaseOptions {
+    /// The longest solution that is accepted.
+    pub max_length: usize,
+    /// The search stops as soon as a solution of at most this length is found.
+    pub target_length: usize,
+    /// The time after which the search stops, or ``None`` to search without a limit.
+    pub time_limit: Option<Duration>,
+}
+
+impl Default for TwoPhaseOptions {
+    /// Returns the first solution of at most 21 moves, however long it takes to find.
+    fn default() -> Self {
+        Self { max_length: 21, target_length: 21, time_limit: None }
+    }
+}
+
+/// Solves a 3x3x3 Cube using Kociemba's Two-Phase Algorithm.
+///
+/// The first phase reduces the cube into the group ``<U, D, R2, L2, F2, B2>``, where
+/// every corner and edge is oriented and the E slice edges are in the E slice. The
+/// second phase then solves the cube using only those moves. Many first phase
+/// solutions are tried, so solutions of around 20 moves are found in a fraction of a
+/// second, and ``TwoPhaseOptions`` can be used to keep searching for shorter ones.
+/// See [Kociemba's page] for the details.
+///
+/// The tables are generated on the first call and kept for all later calls in the
+/// process, which takes under a second in release builds. They are generated again in
+/// every process, unless ``cache_two_phase_tables`` is called first to load them from
+/// disk. Cubes which are not in standard
+/// orientation are first rotated into it, so the solution may begin with whole cube
+/// rotations.
+///
+/// Returns ``None`` if the cube is not a solvable 3x3x3, or if no solution was found
+/// within the limits of the options.
+///
+/// [Kociemba's page]: http://kociemba.org/cube.htm
+///
+/// # Examples
+///
+/// Solve the superflip position:
+///
+/// ```rust
+/// use cubesim::prelude::{Cube};
+/// use cubesim::{FaceletCube, TwoPhaseOptions, parse_scramble, solve_two_phase};
+///
+/// let cube = FaceletCube::new(3).apply_moves(
+///     &parse_scramble(String::from("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2"))
+/// );
+/// let solution = solve_two_phase(&cube, &TwoPhaseOptions::default()).unwrap();
+/// assert!(solution.len() <= 21);
+/// assert!(cube.apply_moves(&solution).is_solved());
+/// ```
+pub fn solve_two_phase(cube: &impl Cube, options: &TwoPhaseOptions) -> Option<Vec<Move>> {
+    if cube.size() != 3 {
+        return None;
+    }
+
+    let mut solution = cube.orientation().unwrap_or_default();
+    let cube = FaceletCube::from_state(&cube.apply_moves(&solution).state())
+        .and_then(|cube| CubieCube::from_facelet_cube(&cube))
+        .filter(CubieCube::is_solvable)?;
+
+    let mut search = Search {
+        tables: tables(),
+        cube: cube.clone(),
+        moves: vec![],
+        phase1_length: 0,
+        best: None,
+        max_length: options.max_length,
+        target_length: options.target_length,
+        deadline: options.time_limit.map(|limit| Instant::now() + limit),
+        done: false,
+    };
+
+    let (twist, flip, slice) = (cube.twist(), cube.flip(), slice_coordinate(&cube));
+    let mut depth = 0;
+
+    while !search.done && depth <= search.max_length {
+        search.phase1_length = depth;
+        search.phase1(twist, flip, slice, depth);
+        depth += 1;
+    }
+
+    solution.extend(search.best?.into_iter().map(face_turn));
+    Some(solution)
+}
+
+/// The number of values of each coordinate.
+const TWISTS: usize = 2187;
+const FLIPS: usize = 2048;
+const SLICES: usize = 495;
+const CORNER_PERMUTATIONS: usize = 40320;
+const EDGE_PERMUTATIONS: usize = 40320;
+const SLICE_PERMUTATIONS: usize = 24;
+
+/// The moves of the second phase, which keep the cube in ``<U, D, R2, L2, F2, B2>``.
+const PHASE2_MOVES: [usize; 10] = [0, 1, 2, 4, 7, 9, 10, 11, 13, 16];
+
+/// The positions of the four E slice edges as a number in ``0..495``, which is 0
+/// when they are all in the E slice.
+fn slice_coordinate(cube: &CubieCube) -> usize {
+    let mut slice = 0;
+    let mut seen = 0;
+
+    for position in (0..12).rev() {
+        if cube.ep[position] >= 8 {
+            seen += 1;
+            slice += binomial(11 - position, seen);
+        }
+    }
+
+    slice
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+}
+
+/// The coordinates of a cube in the second phase: the corner permutation, the
+/// permutation of the U and D layer edges, and the permutation of the E slice edges.
+fn phase2_coordinates(cube: &CubieCube) -> (usize, usize, usize) {
+    (permutation_rank(&cube.cp), permutation_rank(&cube.ep[..8]), permutation_rank(&cube.ep[8..]))
+}
+
+/// Loads the tables of ``solve_two_phase`` from ``cache_dir``, generating and saving
+/// any tables which are missing, so that later processes do not generate them again.
+///
+/// Does nothing if the tables have already been loaded or generated in this process.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::cache_two_phase_tables;
+///
+/// cache_two_phase_tables(&std::env::temp_dir().join("cubesim")).unwrap();
+/// ```
+pub fn cache_two_phase_tables(cache_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(cache_dir)?;
+    let mut result = Ok(());
+
+    TABLES.get_or_init(|| Tables::build(|name, bytes, generate| cached_table(cache_dir, name, bytes, generate, &mut result)));
+
+    result
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+fn tables() -> &'static Tables {
+    TABLES.get_or_init(|| Tables::build(|_, _, generate| generate()))
+}
+
+/// The move and pruning tables of both phases.
+///
+/// A move table gives the coordinate reached by applying each move to each value
+/// of a coordinate. A pruning table gives the number of moves needed to solve a
+/// pair of coordinates, which is a lower bound on the length of the phase.
+struct Tables {
+    move_cubes: Vec<CubieCube>,
+    twist_moves: Vec<u16>,
+    flip_moves: Vec<u16>,
+    slice_moves: Vec<u16>,
+    corner_moves: Vec<u16>,
+    edge_moves: Vec<u16>,
+    slice_permutation_moves: Vec<u16>,
+    slice_twist_pruning: Vec<u8>,
+    slice_flip_pruning: Vec<u8>,
+    slice_corner_pruning: Vec<u8>,
+    slice_edge_pruning: Vec<u8>,
+}
+
+impl Tables {
+    /// Builds the tables, getting the data of each table from ``load``. It is given
+    /// the name and size of the table, and a function to generate its data.
+    fn build(mut load: impl FnMut(&str, usize, &dyn Fn() -> Vec<u8>) -> Vec<u8>) -> Self {
+        let move_cubes = (0..FACE_TURNS).map(|m| CubieCube::from_move(face_turn(m))).collect::<Vec<_>>();
+        let all_moves = (0..FACE_TURNS).collect::<Vec<_>>();
+
+        let mut moves = |name: &str, size: usize, moves: &[usize], coordinate: &dyn Fn(&CubieCube) -> usize| {
+            let data = load(name, 2 * size * FACE_TURNS, &|| {
+                move_table(&move_cubes, size, moves, coordinate).iter().flat_map(|m| m.to_le_bytes()).collect()
+            });
+            data.chunks(2).map(|m| u16::from_le_bytes([m[0], m[1]])).collect::<Vec<_>>()
+        };
+
+        let twist_moves = moves("two-phase-twist-moves", TWISTS, &all_moves, &CubieCube::twist);
+        let flip_moves = moves("two-phase-flip-moves", FLIPS, &all_moves, &CubieCube::flip);
+        let slice_moves = moves("two-phase-slice-moves", SLICES, &all_moves, &slice_coordinate);
+        let corner_moves = moves("two-phase-corner-moves", CORNER_PERMUTATIONS, &PHASE2_MOVES, &|c| phase2_coordinates(c).0);
+        let edge_moves = moves("two-phase-edge-moves", EDGE_PERMUTATIONS, &PHASE2_MOVES, &|c| phase2_coordinates(c).1);
+        let slice_permutation_moves =
+            moves("two-phase-slice-permutation-moves", SLICE_PERMUTATIONS, &PHASE2_MOVES, &|c| phase2_coordinates(c).2);
+
+        let mut pruning = |name: &str, first: (&[u16], usize), second: (&[u16], usize), moves: &[usize]| {
+            load(name, first.1 * second.1, &|| pruning_table(first, second, moves))
+        };
+
+        Self {
+            slice_twist_pruning: pruning("two-phase-slice-twist", (&slice_moves, SLICES), (&twist_moves, TWISTS), &all_moves),
+            slice_flip_pruning: pruning("two-phase-slice-flip", (&slice_moves, SLICES), (&flip_moves, FLIPS), &all_moves),
+            slice_corner_pruning: pruning(
+                "two-phase-slice-corner",
+                (&slice_permutation_moves, SLICE_PERMUTATIONS), (&corner_moves, CORNER_PERMUTATIONS), &PHASE2_MOVES
+            ),
+            slice_edge_pruning: pruning(
+                "two-phase-slice-edge",
+                (&slice_permutation_moves, SLICE_PERMUTATIONS), (&edge_moves, EDGE_PERMUTATIONS), &PHASE2_MOVES
+            ),
+            move_cubes,
+            twist_moves,
+            flip_moves,
+            slice_moves,
+            corner_moves,
+            edge_moves,
+            slice_permutation_moves,
+        }
+    }
+
+    fn phase1_bound(&self, twist: usize, flip: usize, slice: usize) -> usize {
+        let by_twist = self.slice_twist_pruning[slice * TWISTS + twist];
+        let by_flip = self.slice_flip_pruning[slice * FLIPS + flip];
+        by_twist.max(by_flip) as usize
+    }
+
+    fn phase2_bound(&self, corners: usize, edges: usize, slice: usize) -> usize {
+        let by_corners = self.slice_corner_pruning[slice * CORNER_PERMUTATIONS + corners];
+        let by_edges = self.slice_edge_pruning[slice * EDGE_PERMUTATIONS + edges];
+        by_corners.max(by_edges) as usize
+    }
+}
+
+/// Builds the pruning table of a pair of coordinates, given by their move tables and sizes.
+fn pruning_table(first: (&[u16], usize), second: (&[u16], usize), moves: &[usize]) -> Vec<u8> {
+    let (first_moves, first_size) = first;
+    let (second_moves, second_size) = second;
+    let mut table = vec![u8::MAX; first_size * second_size];
+    let mut frontier = vec![0];
+    let mut depth = 0;
+    table[0] = 0;
+
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next_frontier = vec![];
+
+        for index in frontier {
+            let (a, b) = (index / second_size, index % second_size);
+
+            for &m in moves {
+                let next = first_moves[a * FACE_TURNS + m] as usize * second_size
+                    + second_moves[b * FACE_TURNS + m] as usize;
+
+                if table[next] == u8::MAX {
+                    table[next] = depth;
+                    next_frontier.push(next);
+                }
+            }
+        }
+
+        frontier = next_frontier;
+    }
+
+    table
+}
+
+struct Search<'a> {
+    tables: &'a Tables,
+    cube: CubieCube,
+    /// The moves of the first phase followed by those of the second phase.
+    moves: Vec<usize>,
+    phase1_length: usize,
+    best: Option<Vec<usize>>,
+    /// The length of the longest solution still worth finding.
+    max_length: usize,
+    target_length: usize,
+    deadline: Option<Instant>,
+    done: bool,
+}
+
+impl Search<'_> {
+    /// Searches for first phase solutions of exactly ``depth`` more moves, and
+    /// continues each of them with the second phase.
+    fn phase1(&mut self, twist: usize, flip: usize, slice: usize, depth: usize) {
+        if depth == 0 {
+            // A first phase solution ending in a second phase move would already
+            // have been continued with the second phase at a shorter depth.
+            let ends_in_phase2 = self.moves.last().is_some_and(|m| PHASE2_MOVES.contains(m));
+
+            if twist == 0 && flip == 0 && slice == 0 && !ends_in_phase2 {
+                self.phase2_start();
+            }
+            return;
+        }
+
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.done = true;
+            return;
+        }
+
+        for m in 0..FACE_TURNS {
+            if !may_follow(self.moves.last().copied(), m) {
+                continue;
+            }
+
+            let tables = self.tables;
+            let twist = tables.twist_moves[twist * FACE_TURNS + m] as usize;
+            let flip = tables.flip_moves[flip * FACE_TURNS + m] as usize;
+            let slice = tables.slice_moves[slice * FACE_TURNS + m] as usize;
+
+            if tables.phase1_bound(twist, flip, slice) < depth {
+                self.moves.push(m);
+                self.phase1(twist, flip, slice, depth - 1);
+                self.moves.pop();
+            }
+
+            if self.done {
+                return;
+            }
+        }
+    }
+
+    /// Searches for the shortest second phase solution continuing the current first
+    /// phase solution which beats the best solution so far.
+    fn phase2_start(&mut self) {
+        if self.max_length < self.phase1_length {
+            return;
+        }
+
+        let cube = self.moves
+            .iter()
+            .fold(self.cube.clone(), |cube, &m| cube.multiply(&self.tables.move_cubes[m]));
+        let (corners, edges, slice) = phase2_coordinates(&cube);
+        let bound = self.tables.phase2_bound(corners, edges, slice);
+
+        for depth in bound..=self.max_length - self.phase1_length {
+            if self.phase2(corners, edges, slice, depth) {
+                let solution = self.moves.clone();
+                self.moves.truncate(self.phase1_length);
+
+                if solution.len() <= self.target_length {
+                    self.done = true;
+                } else {
+                    self.max_length = solution.len() - 1;
+                }
+
+                self.best = Some(solution);
+                return;
+            }
+        }
+    }
+
+    /// Searches for second phase solutions of exactly ``depth`` more moves, leaving
+    /// the first one found at the end of ``moves``.
+    fn phase2(&mut self, corners: usize, edges: usize, slice: usize, depth: usize) -> bool {
+        if depth == 0 {
+            return corners == 0 && edges == 0 && slice == 0;
+        }
+
+        for &m in &PHASE2_MOVES {
+            if !may_follow(self.moves.last().copied(), m) {
+                continue;
+            }
+
+            let tables = self.tables;
+            let corners = tables.corner_moves[corners * FACE_TURNS + m] as usize;
+            let edges = tables.edge_moves[edges * FACE_TURNS + m] as usize;
+            let slice = tables.slice_permutation_moves[slice * FACE_TURNS + m] as usize;
+
+            if tables.phase2_bound(corners, edges, slice) < depth {
+                self.moves.push(m);
+
+                if self.phase2(corners, edges, slice, depth - 1) {
+                    return true;
+                }
+
+                self.moves.pop();
+            }
+        }
+
+        false
+    }
+}
@@ -18,20 +18,59 @@
 //! which define the minimal set of behaviours expected of a Rubik's Cube. Specific implementations 
 //! can then be used for different scenarios that fit its characteristics. For example, the ``FaceletCube``
 //! is the most performant and versatile while the ``GeoCube`` allows for easy 3D modelling.
+//!
+//! # Optional Features
+//! - ``serde``: implements ``Serialize`` and ``Deserialize`` for ``Face``, ``MoveVariant``,
+//!   ``Move``, ``FaceletCube``, ``GeoCube`` and ``PruningTable``.
 
 #[doc(hidden)]
 pub mod prelude;
 
-pub use scramble_parser::{parse_scramble, simplify_moves};
-pub use generic_cube::{Cube, Face, Move, MoveVariant, solved_state, all_moves, sticker_index};
+pub use scramble_parser::{parse_scramble, simplify_moves, invert_moves};
+pub use generic_cube::{Cube, Face, Move, MoveVariant, MatchMode, solved_state, all_moves, all_rotations, sticker_index};
 pub use facelet_cube::FaceletCube;
-pub use geometric_cube::GeoCube;
+pub use geometric_cube::{GeoCube, Easing, StickerTransform, Timeline, StickerHit};
+pub use symmetry::Symmetry;
+pub use inference::{Completion, complete_state};
+pub use pieces::{PieceType, piece_type, sticker_orbit, orbit_count, stickers_of, orbit_stickers};
+pub use color_scheme::{Color, ColorScheme};
+pub use render::{terminal_net, Arrow, SvgOptions, svg_net, svg_isometric};
+pub use render::{svg_last_layer, svg_last_layer_case, last_layer_arrows};
+pub use render::{PngOptions, png_net, png_isometric};
+pub use render::{ObjMesh, obj_mesh, gltf_mesh};
+pub use render::{AnimationOptions, svg_animation};
+pub use stages::{
+    is_cross_solved, f2l_pairs_solved, is_f2l_solved, is_oll_solved,
+    is_pll_solved, is_eo_solved, is_first_block_solved
+};
 pub use thistlethwaite::solve;
+pub use kociemba::{TwoPhaseOptions, cache_two_phase_tables, solve_two_phase};
+pub use optimal::OptimalSolver;
+pub use pocket_cube::{Metric, solve_2x2, distance_2x2};
+pub use reduction::{solve_4x4, solve_nxn};
 pub use generic_solver::{Solver, PruningTable};
+pub use scrambler::{
+    random_state, random_state_scramble, random_state_2x2, random_state_scramble_2x2,
+    random_move_scramble, wca_random_move_scramble, WCA_SCRAMBLE_LENGTHS
+};
 
 mod generic_cube;
+mod color_scheme;
 mod generic_solver;
+mod cubie_cube;
 mod facelet_cube;
 mod geometric_cube;
+mod inference;
+mod kociemba;
+mod optimal;
+mod pieces;
+mod pocket_cube;
+mod reduction;
+mod render;
 mod scramble_parser;
+mod scrambler;
+#[cfg(feature = "serde")]
+mod serialization;
+mod stages;
+mod symmetry;
 mod thistlethwaite;
\ No newline at end of file
@@ -1,14 +1,5 @@
-mod generic_cube;
-mod geometric_cube;
-mod facelet_cube;
-mod generic_solver;
-mod thistlethwaite;
-mod scramble_parser;
-
-use generic_cube::{Cube, Move, MoveVariant};
-use facelet_cube::FaceletCube;
-use thistlethwaite::solve;
-use scramble_parser::parse_scramble;
+use cubesim::prelude::Cube;
+use cubesim::{FaceletCube, parse_scramble, solve};
 
 fn main() {
     let cube = &FaceletCube::new(3).apply_moves(&parse_scramble(String::from("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2")));
@@ -0,0 +1,575 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::generic_cube::{Cube, Move};
+use crate::facelet_cube::FaceletCube;
+use crate::cubie_cube::{CubieCube, FACE_TURNS, cached_table, face_turn, may_follow, move_table};
+use crate::cubie_cube::{permutation_rank, permutation_unrank};
+use crate::scramble_parser::parse_scramble;
+
+/// An optimal 3x3x3 solver in the style of [Korf's algorithm].
+///
+/// An IDA* search is guided by pattern databases, which give the exact number of
+/// moves needed to solve the corners and two groups of edges. The largest of these
+/// is a lower bound on the length of a solution, so the first solution found is
+/// optimal in the half turn metric.
+///
+/// The corner database is reduced by the 8 symmetries of the cube which keep the
+/// U and D faces on the U-D axis, and the same edge database is looked up for both
+/// groups of edges by looking at the cube through a z2 rotation. Tables are generated
+/// locally, and can be cached on disk to avoid generating them again.
+///
+/// # Memory and Time Budget
+///
+/// The corner database takes about 5.6 MB and a few seconds to generate. The size of
+/// the edge database depends on the number of edges in each group, with the total
+/// given by ``OptimalSolver::table_size``:
+///
+/// | Edges | Edge database | Generation time (release build) |
+/// |-------|---------------|---------------------------------|
+/// | 4     | 95 KB         | instant                         |
+/// | 5     | 1.5 MB        | about a second                  |
+/// | 6     | 21 MB         | about half a minute             |
+/// | 7     | 255 MB        | several minutes                 |
+///
+/// Larger databases prune more of the search, making solves faster. Even so, an
+/// optimal solution of a random state can take minutes to hours to find, as the
+/// search grows by a factor of about 13 with every extra move. A time limit should
+/// be given when solving states whose distance is unknown.
+///
+/// [Korf's algorithm]: https://www.cs.princeton.edu/courses/archive/fall06/cos402/papers/korfrubik.pdf
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, OptimalSolver, parse_scramble};
+///
+/// let solver = OptimalSolver::cached(4, &std::env::temp_dir().join("cubesim")).unwrap();
+/// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R' U'")));
+/// let solution = solver.solve(&cube, None).unwrap();
+///
+/// assert_eq!(solution.len(), 4);
+/// assert!(cube.apply_moves(&solution).is_solved());
+/// ```
+pub struct OptimalSolver {
+    edge_pieces: usize,
+    corner_moves: Vec<u16>,
+    twist_moves: Vec<u16>,
+    /// The position each face turn takes an edge to from each position, along with
+    /// the flip it adds.
+    edge_moves: Vec<[(u8, u8); 12]>,
+    /// The edge position each edge position is taken to by a z2 rotation.
+    mirrored_edges: [u8; 12],
+    /// The symmetry class of each corner permutation and the symmetry taking it to
+    /// the representative of the class.
+    corner_classes: Vec<(u16, u8)>,
+    /// The twist of a cube after conjugating by each symmetry, at ``twist * 8 + symmetry``.
+    twist_conjugates: Vec<u16>,
+    corner_table: DistanceTable,
+    edge_table: DistanceTable,
+}
+
+/// Whole cube rotations which keep the U and D faces on the U-D axis.
+const SYMMETRIES: [&str; 8] = ["", "y", "y2", "y'", "x2", "x2 y", "x2 y2", "x2 y'"];
+
+/// The index of z2 in ``SYMMETRIES``, which swaps the two groups of edges.
+const Z2: usize = 6;
+
+/// The edges of the first group, up to the number of edges in a group. A z2 rotation
+/// takes the first six to the other six, which make up the second group.
+const EDGE_GROUP: [u8; 7] = [0, 1, 2, 3, 8, 10, 4];
+
+const TWISTS: usize = 2187;
+const CORNER_PERMUTATIONS: usize = 40320;
+
+/// The longest optimal solution of any 3x3x3 state.
+const GODS_NUMBER: usize = 20;
+
+impl OptimalSolver {
+    /// Generates the tables of a solver tracking ``edge_pieces`` edges in each edge group.
+    ///
+    /// Panics if ``edge_pieces`` is not between 1 and 7.
+    pub fn new(edge_pieces: usize) -> Self {
+        Self::build(edge_pieces, |_, _, generate| generate())
+    }
+
+    /// Loads the tables of a solver from ``cache_dir``, generating and saving any
+    /// tables which are missing.
+    ///
+    /// Panics if ``edge_pieces`` is not between 1 and 7.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::OptimalSolver;
+    ///
+    /// let solver = OptimalSolver::cached(4, &std::env::temp_dir().join("cubesim")).unwrap();
+    /// ```
+    pub fn cached(edge_pieces: usize, cache_dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(cache_dir)?;
+        let mut result = Ok(());
+
+        let solver = Self::build(edge_pieces, |name, bytes, generate| {
+            cached_table(cache_dir, name, bytes, generate, &mut result)
+        });
+
+        result.map(|_| solver)
+    }
+
+    /// The number of bytes taken by the tables of a solver tracking ``edge_pieces``
+    /// edges in each edge group.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::OptimalSolver;
+    ///
+    /// assert!(OptimalSolver::table_size(6) < 30_000_000);
+    /// ```
+    pub fn table_size(edge_pieces: usize) -> usize {
+        assert!((1..=7).contains(&edge_pieces), "edge groups must have between 1 and 7 edges");
+
+        let corner_classes = corner_symmetry_classes(&symmetry_cubes().0).1.len();
+        DistanceTable::bytes(corner_classes * TWISTS) + DistanceTable::bytes(edge_states(edge_pieces))
+    }
+
+    /// Finds an optimal solution of a cube in the half turn metric.
+    ///
+    /// Cubes which are not in standard orientation are first rotated into it, so the
+    /// solution may begin with whole cube rotations, which are not counted as moves.
+    ///
+    /// Returns ``None`` if the cube is not a solvable 3x3x3, or if ``time_limit``
+    /// passes before an optimal solution is found.
+    pub fn solve(&self, cube: &impl Cube, time_limit: Option<Duration>) -> Option<Vec<Move>> {
+        if cube.size() != 3 {
+            return None;
+        }
+
+        let mut solution = cube.orientation().unwrap_or_default();
+        let cube = FaceletCube::from_state(&cube.apply_moves(&solution).state())
+            .and_then(|cube| CubieCube::from_facelet_cube(&cube))
+            .filter(CubieCube::is_solvable)?;
+
+        let mut search = Search {
+            solver: self,
+            solved: Position::new(&CubieCube::solved()),
+            moves: vec![],
+            deadline: time_limit.map(|limit| Instant::now() + limit),
+            nodes: 0,
+            timed_out: false,
+        };
+        let position = Position::new(&cube);
+
+        for depth in self.bound(&position, GODS_NUMBER)..=GODS_NUMBER {
+            if search.search(&position, depth) {
+                solution.extend(search.moves.into_iter().map(face_turn));
+                return Some(solution);
+            }
+
+            if search.timed_out {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Builds a solver, getting the data of each table from ``load``. It is given
+    /// the name and size of the table, and a function to generate its data.
+    fn build(edge_pieces: usize, mut load: impl FnMut(&str, usize, &dyn Fn() -> Vec<u8>) -> Vec<u8>) -> Self {
+        assert!((1..=7).contains(&edge_pieces), "edge groups must have between 1 and 7 edges");
+
+        let move_cubes = (0..FACE_TURNS).map(|m| CubieCube::from_move(face_turn(m))).collect::<Vec<_>>();
+        let all_moves = (0..FACE_TURNS).collect::<Vec<_>>();
+        let (symmetries, symmetry_inverses) = symmetry_cubes();
+        let (corner_classes, representatives) = corner_symmetry_classes(&symmetries);
+
+        let edge_moves = move_cubes
+            .iter()
+            .map(|mv| {
+                let mut targets = [(0, 0); 12];
+                for position in 0..12 {
+                    targets[mv.ep[position] as usize] = (position as u8, mv.eo[position]);
+                }
+                targets
+            })
+            .collect();
+
+        let mut solver = Self {
+            edge_pieces,
+            corner_moves: move_table(&move_cubes, CORNER_PERMUTATIONS, &all_moves, |c| permutation_rank(&c.cp)),
+            twist_moves: move_table(&move_cubes, TWISTS, &all_moves, CubieCube::twist),
+            edge_moves,
+            mirrored_edges: symmetries[Z2].ep,
+            corner_classes,
+            twist_conjugates: twist_conjugates(&symmetries, &symmetry_inverses),
+            corner_table: DistanceTable::new(0),
+            edge_table: DistanceTable::new(0),
+        };
+
+        let corner_states = representatives.len() * TWISTS;
+        let data = load("optimal-corners", DistanceTable::bytes(corner_states), &|| {
+            solver.generate_corner_table(&representatives).data
+        });
+        solver.corner_table = DistanceTable { data };
+
+        let edge_states = edge_states(edge_pieces);
+        let data = load(&format!("optimal-edges-{}", edge_pieces), DistanceTable::bytes(edge_states), &|| {
+            solver.generate_edge_table().data
+        });
+        solver.edge_table = DistanceTable { data };
+
+        solver
+    }
+
+    /// A lower bound on the number of moves needed to solve a position. Once the
+    /// bound is known to exceed ``limit``, it is returned without looking further.
+    fn bound(&self, position: &Position, limit: usize) -> usize {
+        let corners = self.corner_table.get(self.corner_index(position.corners, position.twist)) as usize;
+        if corners > limit {
+            return corners;
+        }
+
+        let edges = self.edge_table.get(self.edge_index(position, false)) as usize;
+        if edges > limit {
+            return edges;
+        }
+
+        let mirrored_edges = self.edge_table.get(self.edge_index(position, true)) as usize;
+        corners.max(edges).max(mirrored_edges)
+    }
+
+    /// The index of a corner permutation and twist in the corner table.
+    fn corner_index(&self, corners: u16, twist: u16) -> usize {
+        let (class, symmetry) = self.corner_classes[corners as usize];
+        class as usize * TWISTS + self.twist_conjugates[twist as usize * 8 + symmetry as usize] as usize
+    }
+
+    /// The index of the positions and flips of the first group of edges in the edge table.
+    ///
+    /// When ``mirrored``, the second group of edges is used instead, as the first group
+    /// of the position seen through a z2 rotation, which keeps the flips of edges.
+    fn edge_index(&self, position: &Position, mirrored: bool) -> usize {
+        let mut positions = [0; 7];
+        let mut flips = 0;
+
+        for (n, &edge) in EDGE_GROUP[..self.edge_pieces].iter().enumerate() {
+            let edge = if mirrored { self.mirrored_edges[edge as usize] } else { edge } as usize;
+            let at = position.edges[edge];
+
+            positions[n] = if mirrored { self.mirrored_edges[at as usize] } else { at };
+            flips |= (position.flips as usize >> edge & 1) << n;
+        }
+
+        partial_permutation_rank(&positions[..self.edge_pieces]) << self.edge_pieces | flips
+    }
+
+    /// Generates the corner table over the representatives of the corner classes.
+    ///
+    /// A representative left unchanged by some symmetries appears with several twists
+    /// which are the same up to symmetry, and so are the same distance from solved.
+    /// Whenever one of these is reached, all of them are, as ``corner_index`` may
+    /// pick any of them.
+    fn generate_corner_table(&self, representatives: &[(u16, u8)]) -> DistanceTable {
+        DistanceTable::generate(representatives.len() * TWISTS, 0, |index, neighbours| {
+            let corners = representatives[index / TWISTS].0 as usize;
+            let twist = index % TWISTS;
+
+            for m in 0..FACE_TURNS {
+                let corners = self.corner_moves[corners * FACE_TURNS + m];
+                let twist = self.twist_moves[twist * FACE_TURNS + m];
+                let next = self.corner_index(corners, twist);
+                let (class, twist) = (next / TWISTS, next % TWISTS);
+
+                neighbours.push(next);
+
+                for s in (1..8).filter(|s| representatives[class].1 >> s & 1 == 1) {
+                    neighbours.push(class * TWISTS + self.twist_conjugates[twist * 8 + s] as usize);
+                }
+            }
+        })
+    }
+
+    fn generate_edge_table(&self) -> DistanceTable {
+        let pieces = self.edge_pieces;
+        let solved = self.edge_index(&Position::new(&CubieCube::solved()), false);
+
+        DistanceTable::generate(edge_states(pieces), solved, |index, neighbours| {
+            let positions = partial_permutation_unrank(index >> pieces, pieces);
+            let flips = index & ((1 << pieces) - 1);
+
+            for targets in &self.edge_moves {
+                let mut moved = [0; 7];
+                let mut moved_flips = 0;
+
+                for (n, &position) in positions[..pieces].iter().enumerate() {
+                    let (target, flip) = targets[position as usize];
+                    moved[n] = target;
+                    moved_flips |= ((flips >> n & 1) ^ flip as usize) << n;
+                }
+
+                neighbours.push(partial_permutation_rank(&moved[..pieces]) << pieces | moved_flips);
+            }
+        })
+    }
+}
+
+/// A cube as seen by the search: the corner permutation and twist, along with the
+/// position of each edge and a bit for the flip of each edge.
+#[derive(Clone, Copy, Eq, PartialEq)]
+struct Position {
+    corners: u16,
+    twist: u16,
+    edges: [u8; 12],
+    flips: u16,
+}
+
+impl Position {
+    fn new(cube: &CubieCube) -> Self {
+        let mut edges = [0; 12];
+        let mut flips = 0;
+
+        for (position, &edge) in cube.ep.iter().enumerate() {
+            edges[edge as usize] = position as u8;
+            flips |= (cube.eo[position] as u16) << edge;
+        }
+
+        Self { corners: permutation_rank(&cube.cp) as u16, twist: cube.twist() as u16, edges, flips }
+    }
+
+    fn apply_move(&self, solver: &OptimalSolver, m: usize) -> Self {
+        let mut edges = self.edges;
+        let mut flips = self.flips;
+
+        for (edge, position) in edges.iter_mut().enumerate() {
+            let (target, flip) = solver.edge_moves[m][*position as usize];
+            *position = target;
+            flips ^= (flip as u16) << edge;
+        }
+
+        Self {
+            corners: solver.corner_moves[self.corners as usize * FACE_TURNS + m],
+            twist: solver.twist_moves[self.twist as usize * FACE_TURNS + m],
+            edges,
+            flips,
+        }
+    }
+}
+
+/// An iterative deepening search for a solution of a given length.
+struct Search<'a> {
+    solver: &'a OptimalSolver,
+    solved: Position,
+    moves: Vec<usize>,
+    deadline: Option<Instant>,
+    nodes: usize,
+    timed_out: bool,
+}
+
+impl Search<'_> {
+    /// Searches for a solution of exactly ``depth`` more moves, leaving it at the end of ``moves``.
+    fn search(&mut self, position: &Position, depth: usize) -> bool {
+        if depth == 0 {
+            return *position == self.solved;
+        }
+
+        self.nodes += 1;
+        if self.nodes % 4096 == 0 && self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.timed_out = true;
+        }
+
+        for m in 0..FACE_TURNS {
+            if self.timed_out {
+                return false;
+            }
+
+            if !may_follow(self.moves.last().copied(), m) {
+                continue;
+            }
+
+            let next = position.apply_move(self.solver, m);
+
+            if self.solver.bound(&next, depth - 1) < depth {
+                self.moves.push(m);
+
+                if self.search(&next, depth - 1) {
+                    return true;
+                }
+
+                self.moves.pop();
+            }
+        }
+
+        false
+    }
+}
+
+/// The number of moves needed to reach each state of a pattern, stored in four bits each.
+struct DistanceTable {
+    data: Vec<u8>,
+}
+
+impl DistanceTable {
+    const UNKNOWN: u8 = 0xF;
+
+    fn new(states: usize) -> Self {
+        Self { data: vec![0xFF; Self::bytes(states)] }
+    }
+
+    fn bytes(states: usize) -> usize {
+        states.div_ceil(2)
+    }
+
+    /// Generates a table by a breadth first search from the solved state.
+    ///
+    /// ``neighbours`` adds the indices of the states reached by each move from a state.
+    fn generate(states: usize, solved: usize, neighbours: impl Fn(usize, &mut Vec<usize>)) -> Self {
+        let mut table = Self::new(states);
+        let mut frontier = vec![solved as u32];
+        let mut reached = vec![];
+        let mut depth = 0;
+        table.set(solved, 0);
+
+        while !frontier.is_empty() {
+            depth += 1;
+            let mut next_frontier = vec![];
+
+            for index in frontier {
+                reached.clear();
+                neighbours(index as usize, &mut reached);
+
+                for &next in &reached {
+                    if table.get(next) == Self::UNKNOWN {
+                        table.set(next, depth);
+                        next_frontier.push(next as u32);
+                    }
+                }
+            }
+
+            frontier = next_frontier;
+        }
+
+        table
+    }
+
+    fn get(&self, index: usize) -> u8 {
+        self.data[index / 2] >> (4 * (index % 2)) & 0xF
+    }
+
+    fn set(&mut self, index: usize, value: u8) {
+        let shift = 4 * (index % 2);
+        self.data[index / 2] = self.data[index / 2] & !(0xF << shift) | value << shift;
+    }
+}
+
+/// The symmetries as cubes, along with their inverses.
+fn symmetry_cubes() -> (Vec<CubieCube>, Vec<CubieCube>) {
+    let symmetries = SYMMETRIES
+        .iter()
+        .map(|rotation| {
+            let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(*rotation)));
+            CubieCube::from_facelet_cube(&cube).unwrap()
+        })
+        .collect::<Vec<_>>();
+    let inverses = symmetries.iter().map(CubieCube::inverse).collect();
+
+    (symmetries, inverses)
+}
+
+/// The class of each corner permutation along with the symmetry taking it to the
+/// representative of its class, and the representative of each class along with a bit
+/// for every symmetry which leaves it unchanged.
+type CornerClasses = (Vec<(u16, u8)>, Vec<(u16, u8)>);
+
+/// Splits the corner permutations into classes of permutations related by symmetry.
+/// The representative of each class is the permutation of lowest rank in the class.
+fn corner_symmetry_classes(symmetries: &[CubieCube]) -> CornerClasses {
+    let inverses = symmetries.iter().map(CubieCube::inverse).collect::<Vec<_>>();
+    let mut classes = vec![(0, 0); CORNER_PERMUTATIONS];
+    let mut representatives = vec![];
+    let mut class_of_representative = vec![u16::MAX; CORNER_PERMUTATIONS];
+
+    for rank in 0..CORNER_PERMUTATIONS {
+        let mut cube = CubieCube::solved();
+        cube.cp.copy_from_slice(&permutation_unrank(rank, 8));
+
+        let conjugates = (0..symmetries.len())
+            .map(|s| permutation_rank(&inverses[s].multiply(&cube).multiply(&symmetries[s]).cp))
+            .collect::<Vec<_>>();
+        let (representative, symmetry) = conjugates.iter().zip(0..).min().map(|(&r, s)| (r, s)).unwrap();
+
+        if representative == rank {
+            let stabilizer = (0..symmetries.len()).filter(|&s| conjugates[s] == rank).fold(0, |bits, s| bits | 1 << s);
+
+            class_of_representative[rank] = representatives.len() as u16;
+            representatives.push((rank as u16, stabilizer));
+        }
+
+        classes[rank] = (class_of_representative[representative], symmetry as u8);
+    }
+
+    (classes, representatives)
+}
+
+/// The twist of each cube after conjugating by each symmetry, at ``twist * 8 + symmetry``.
+///
+/// The symmetries keep the U and D faces on the U-D axis, so the twists do not depend
+/// on the permutation of the corners.
+fn twist_conjugates(symmetries: &[CubieCube], inverses: &[CubieCube]) -> Vec<u16> {
+    let mut table = vec![0; TWISTS * symmetries.len()];
+
+    for twist in 0..TWISTS {
+        let mut cube = CubieCube::solved();
+        let mut rest = twist;
+
+        for i in (0..7).rev() {
+            cube.co[i] = (rest % 3) as u8;
+            rest /= 3;
+        }
+        cube.co[7] = (3 - cube.co[..7].iter().sum::<u8>() % 3) % 3;
+
+        for s in 0..symmetries.len() {
+            table[twist * symmetries.len() + s] = inverses[s].multiply(&cube).multiply(&symmetries[s]).twist() as u16;
+        }
+    }
+
+    table
+}
+
+/// The number of states of a group of edges, by their positions and flips.
+fn edge_states(pieces: usize) -> usize {
+    (12 - pieces + 1..=12).product::<usize>() << pieces
+}
+
+/// The rank of a list of distinct positions among the 12 edge positions.
+fn partial_permutation_rank(positions: &[u8]) -> usize {
+    positions.iter().enumerate().fold(0, |rank, (i, &p)| {
+        let earlier_below = positions[..i].iter().filter(|&&q| q < p).count();
+        rank * (12 - i) + p as usize - earlier_below
+    })
+}
+
+/// The positions with the given rank, the inverse of ``partial_permutation_rank``.
+fn partial_permutation_unrank(mut rank: usize, pieces: usize) -> [u8; 7] {
+    let mut digits = [0; 7];
+
+    for i in (0..pieces).rev() {
+        digits[i] = rank % (12 - i);
+        rank /= 12 - i;
+    }
+
+    // Each digit counts the unused positions below the position it stands for.
+    let mut used = [false; 12];
+    let mut positions = [0; 7];
+
+    for (i, &digit) in digits[..pieces].iter().enumerate() {
+        let position = (0..12).filter(|&p| !used[p]).nth(digit).unwrap();
+        used[position] = true;
+        positions[i] = position as u8;
+    }
+
+    positions
+}
@@ -0,0 +1,217 @@
+use cgmath::Vector3;
+use rustc_hash::FxHashMap;
+
+use crate::generic_cube::{Face, CubeSize, ORDERED_FACES};
+use crate::geometric_cube::Sticker;
+
+/// The type of piece that a sticker of a NxNxN Rubik's Cube belongs to.
+///
+/// Edges are split into midges, the middle edges of odd cubes, and wings. Centers are
+/// split into the fixed center of odd cubes, x-centers on the diagonals of a face,
+/// t-centers on the middle row and column of odd cubes and obliques everywhere else.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PieceType {
+    Center,
+    Corner,
+    Midge,
+    Wing,
+    XCenter,
+    TCenter,
+    Oblique,
+}
+
+/// Get the type of piece that a sticker belongs to.
+///
+/// The sticker is given by its index, following the ordering of ``Cube::state``.
+///
+/// # Panics
+///
+/// Panics if ``size`` is not positive or ``index`` is not a sticker of the cube.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Face;
+/// use cubesim::{PieceType, piece_type, sticker_index};
+///
+/// assert_eq!(piece_type(3, sticker_index(3, Face::F, 1)), PieceType::Corner);
+/// assert_eq!(piece_type(3, sticker_index(3, Face::F, 2)), PieceType::Midge);
+/// assert_eq!(piece_type(4, sticker_index(4, Face::F, 2)), PieceType::Wing);
+/// assert_eq!(piece_type(5, sticker_index(5, Face::F, 8)), PieceType::TCenter);
+/// ```
+pub fn piece_type(size: CubeSize, index: CubeSize) -> PieceType {
+    classify(size, index).0
+}
+
+/// Get the orbit of a sticker among the stickers of the same piece type.
+///
+/// Two stickers are in the same orbit if moves can take one to the position of the
+/// other. Orbits are numbered from 0, starting from the orbit closest to the corners
+/// of each face. Centers, corners and midges always form a single orbit.
+///
+/// # Panics
+///
+/// Panics if ``size`` is not positive or ``index`` is not a sticker of the cube.
+///
+/// # Examples
+///
+/// The two wing orbits of a 6x6x6:
+///
+/// ```rust
+/// use cubesim::prelude::Face;
+/// use cubesim::{sticker_orbit, sticker_index};
+///
+/// assert_eq!(sticker_orbit(6, sticker_index(6, Face::U, 2)), 0);
+/// assert_eq!(sticker_orbit(6, sticker_index(6, Face::U, 3)), 1);
+/// assert_eq!(sticker_orbit(6, sticker_index(6, Face::U, 4)), 1);
+/// assert_eq!(sticker_orbit(6, sticker_index(6, Face::U, 5)), 0);
+/// ```
+pub fn sticker_orbit(size: CubeSize, index: CubeSize) -> CubeSize {
+    let (piece_type, key) = classify(size, index);
+
+    orbit_keys(size, piece_type).iter().position(|&k| k == key).unwrap() as CubeSize
+}
+
+/// Get the number of orbits of a piece type on a cube of the given size.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{PieceType, orbit_count};
+///
+/// assert_eq!(orbit_count(7, PieceType::Wing), 2);
+/// assert_eq!(orbit_count(7, PieceType::Oblique), 2);
+/// assert_eq!(orbit_count(3, PieceType::Wing), 0);
+/// ```
+pub fn orbit_count(size: CubeSize, piece_type: PieceType) -> CubeSize {
+    orbit_keys(size, piece_type).len() as CubeSize
+}
+
+/// Get the indices of all stickers of a piece type.
+///
+/// # Examples
+///
+/// Mask everything but the corners:
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face};
+/// use cubesim::{FaceletCube, PieceType, stickers_of};
+///
+/// let corners = stickers_of(5, PieceType::Corner);
+/// assert_eq!(corners.len(), 24);
+///
+/// let cube = FaceletCube::new(5).mask(&|i, f| if corners.contains(&i) { f } else { Face::X });
+/// assert_eq!(cube.state().iter().filter(|&&f| f != Face::X).count(), 24);
+/// ```
+pub fn stickers_of(size: CubeSize, piece_type: PieceType) -> Vec<CubeSize> {
+    (0..6 * size * size)
+        .filter(|&i| classify(size, i).0 == piece_type)
+        .collect()
+}
+
+/// Get the indices of all stickers in one orbit of a piece type.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{PieceType, orbit_stickers};
+///
+/// assert_eq!(orbit_stickers(6, PieceType::XCenter, 0).len(), 24);
+/// assert_eq!(orbit_stickers(6, PieceType::Wing, 1).len(), 48);
+/// assert!(orbit_stickers(6, PieceType::Wing, 2).is_empty());
+/// ```
+pub fn orbit_stickers(size: CubeSize, piece_type: PieceType, orbit: CubeSize) -> Vec<CubeSize> {
+    let key = match orbit_keys(size, piece_type).get(orbit as usize) {
+        Some(&key) => key,
+        None => return vec![],
+    };
+
+    (0..6 * size * size)
+        .filter(|&i| classify(size, i) == (piece_type, key))
+        .collect()
+}
+
+/// The stickers of every piece of a cube, following the ordering of ``Cube::state``.
+///
+/// Each piece is listed along with its position, where the coordinates of a piece on
+/// a face lie at ``size - 1`` or ``-(size - 1)``. Pieces are ordered by their first sticker.
+pub(crate) fn pieces(size: CubeSize) -> Vec<(Vector3<CubeSize>, Vec<CubeSize>)> {
+    let mut pieces: FxHashMap<Vector3<CubeSize>, Vec<CubeSize>> = FxHashMap::default();
+
+    for index in 0..6 * size * size {
+        let position = Sticker::index_position(size, index).map(|c| c.clamp(1 - size, size - 1));
+        pieces.entry(position).or_default().push(index);
+    }
+
+    let mut pieces = pieces.into_iter().collect::<Vec<_>>();
+    pieces.sort_unstable_by_key(|(_, stickers)| stickers[0]);
+    pieces
+}
+
+/// The outward normal of the face a sticker lies on.
+pub(crate) fn sticker_normal(size: CubeSize, index: CubeSize) -> Vector3<CubeSize> {
+    match ORDERED_FACES[(index / (size * size)) as usize] {
+        Face::R => Vector3::new(1, 0, 0),
+        Face::L => Vector3::new(-1, 0, 0),
+        Face::U => Vector3::new(0, 1, 0),
+        Face::D => Vector3::new(0, -1, 0),
+        Face::F => Vector3::new(0, 0, 1),
+        Face::B => Vector3::new(0, 0, -1),
+        Face::X => unreachable!(),
+    }
+}
+
+/// The piece type of a sticker together with a key identifying its orbit.
+///
+/// Every face has the same layout of pieces, so only the position on the face matters.
+/// Positions are measured from the center of the face in units of half a sticker, so
+/// ``n - 1`` is the border of the face. Turning a face takes ``(a, b)`` to ``(b, -a)``.
+fn classify(size: CubeSize, index: CubeSize) -> (PieceType, (CubeSize, CubeSize)) {
+    assert!(size > 0 && (0..6 * size * size).contains(&index));
+
+    let row = index % (size * size) / size;
+    let col = index % size;
+    let (a, b) = (2 * row - size + 1, 2 * col - size + 1);
+    let border = size - 1;
+
+    // The distance of a coordinate from the border of the face, in stickers.
+    let depth = |c: CubeSize| (border - c.abs()) / 2;
+
+    if size == 1 || (a == 0 && b == 0) {
+        (PieceType::Center, (0, 0))
+    } else if a.abs() == border && b.abs() == border {
+        (PieceType::Corner, (0, 0))
+    } else if a.abs() == border || b.abs() == border {
+        let along = if a.abs() == border { b } else { a };
+
+        if along == 0 { (PieceType::Midge, (0, 0)) } else { (PieceType::Wing, (depth(along), 0)) }
+    } else if a.abs() == b.abs() {
+        (PieceType::XCenter, (depth(a), 0))
+    } else if a == 0 || b == 0 {
+        (PieceType::TCenter, (depth(a + b), 0))
+    } else {
+        // Rotate the position into the quadrant where both coordinates are positive,
+        // which tells the two mirror image orbits with the same depths apart.
+        let (x, y) = match (a > 0, b > 0) {
+            (true, true) => (a, b),
+            (true, false) => (-b, a),
+            (false, false) => (-a, -b),
+            (false, true) => (b, -a),
+        };
+
+        (PieceType::Oblique, (depth(x), depth(y)))
+    }
+}
+
+/// The keys of the orbits of a piece type, sorted from the corners of the face inwards.
+fn orbit_keys(size: CubeSize, piece_type: PieceType) -> Vec<(CubeSize, CubeSize)> {
+    let mut keys = (0..size * size)
+        .map(|i| classify(size, i))
+        .filter(|(t, _)| *t == piece_type)
+        .map(|(_, key)| key)
+        .collect::<Vec<_>>();
+
+    keys.sort_unstable();
+    keys.dedup();
+    keys
+}
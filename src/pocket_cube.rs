@@ -0,0 +1,259 @@
+use lazy_static::lazy_static;
+use rand::Rng;
+
+use crate::generic_cube::{Cube, Face, Move, CubeSize};
+use crate::generic_cube::{sticker_index as S};
+use crate::facelet_cube::FaceletCube;
+use crate::cubie_cube::{
+    CubieCube, CORNER_FACELETS, FACE_TURNS, corner_index_2x2, face_turn, move_table, permutation_rank, permutation_unrank
+};
+
+/// The metric in which the length of a solution is measured.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Metric {
+    /// Every face turn counts as one move, including half turns.
+    HalfTurn,
+    /// Only quarter turns are used, so a half turn counts as two moves.
+    QuarterTurn,
+}
+
+/// Finds an optimal solution of a 2x2x2 cube in the given metric.
+///
+/// The DBL corner is kept in place, so every state is solved with U, R and F turns
+/// alone. Cubes whose DBL corner is elsewhere are first rotated to bring it into
+/// place, so the solution may begin with whole cube rotations, which are not
+/// counted as moves. The solutions are read off a table of the distances of all
+/// 3,674,160 states, which is generated by a breadth first search on the first
+/// call for each metric and kept for later calls.
+///
+/// Returns ``None`` if the cube is not a solvable 2x2x2.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, Metric, parse_scramble, solve_2x2};
+///
+/// let cube = FaceletCube::new(2).apply_moves(&parse_scramble(String::from("R U2 F' R2")));
+///
+/// let solution = solve_2x2(&cube, Metric::HalfTurn).unwrap();
+/// assert_eq!(solution.len(), 4);
+/// assert!(cube.apply_moves(&solution).is_solved());
+///
+/// let solution = solve_2x2(&cube, Metric::QuarterTurn).unwrap();
+/// assert_eq!(solution.len(), 6);
+/// assert!(cube.apply_moves(&solution).is_solved());
+/// ```
+pub fn solve_2x2(cube: &impl Cube, metric: Metric) -> Option<Vec<Move>> {
+    let mut solution = cube.orientation()?;
+    let mut state = coordinate(&cube.apply_moves(&solution))?;
+    let tables = tables(metric);
+
+    while tables.distances[state] > 0 {
+        let m = tables
+            .moves
+            .iter()
+            .copied()
+            .find(|&m| tables.distances[apply(state, m)] < tables.distances[state])
+            .unwrap();
+
+        state = apply(state, m);
+        solution.push(face_turn(m));
+    }
+
+    Some(solution)
+}
+
+/// Gets the number of moves in an optimal solution of a 2x2x2 cube, in the given metric.
+///
+/// Whole cube rotations are not counted. Returns ``None`` if the cube is not a
+/// solvable 2x2x2.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, Metric, parse_scramble, distance_2x2};
+///
+/// // Turning opposite faces of a 2x2x2 in the same direction is a rotation.
+/// let cube = FaceletCube::new(2).apply_moves(&parse_scramble(String::from("R L'")));
+/// assert_eq!(distance_2x2(&cube, Metric::HalfTurn), Some(0));
+///
+/// let cube = FaceletCube::new(2).apply_moves(&parse_scramble(String::from("R L")));
+/// assert_eq!(distance_2x2(&cube, Metric::HalfTurn), Some(1));
+/// assert_eq!(distance_2x2(&cube, Metric::QuarterTurn), Some(2));
+/// ```
+pub fn distance_2x2(cube: &impl Cube, metric: Metric) -> Option<usize> {
+    let rotation = cube.orientation()?;
+    let state = coordinate(&cube.apply_moves(&rotation))?;
+
+    Some(tables(metric).distances[state] as usize)
+}
+
+/// Generates a uniformly random 2x2x2 cube with the DBL corner in place, which takes
+/// at least ``min_moves`` moves to solve in the half turn metric.
+///
+/// States are drawn as coordinates and checked against the distance table, so only
+/// the accepted state is converted into a cube.
+pub(crate) fn random_cube(rng: &mut impl Rng, min_moves: u8) -> FaceletCube {
+    let distances = &tables(Metric::HalfTurn).distances;
+
+    loop {
+        let state = rng.gen_range(0..STATES);
+
+        if distances[state] >= min_moves {
+            return cube(state);
+        }
+    }
+}
+
+/// The number of states of the 2x2x2 with the DBL corner in place.
+const STATES: usize = 5040 * 729;
+
+/// The position of the DBL corner, which U, R and F turns keep in place.
+const DBL: usize = 6;
+
+/// The distances of all states in a metric, along with the moves of the metric.
+struct Tables {
+    moves: Vec<usize>,
+    distances: Vec<u8>,
+}
+
+lazy_static! {
+    /// The effect of each face turn on the corner permutation and twist coordinates.
+    static ref MOVE_TABLES: (Vec<u16>, Vec<u16>) = {
+        let move_cubes = (0..FACE_TURNS).map(|m| CubieCube::from_move(face_turn(m))).collect::<Vec<_>>();
+        let moves = (0..9).collect::<Vec<_>>();
+
+        (
+            move_table(&move_cubes, 5040, &moves, |cube| permutation_rank(&without_dbl(&cube.cp))),
+            move_table(&move_cubes, 729, &moves, |cube| cube.twist() / 3),
+        )
+    };
+
+    static ref HALF_TURN_TABLES: Tables = Tables::generate((0..9).collect());
+
+    static ref QUARTER_TURN_TABLES: Tables = Tables::generate(vec![0, 2, 3, 5, 6, 8]);
+}
+
+fn tables(metric: Metric) -> &'static Tables {
+    match metric {
+        Metric::HalfTurn => &HALF_TURN_TABLES,
+        Metric::QuarterTurn => &QUARTER_TURN_TABLES,
+    }
+}
+
+impl Tables {
+    fn generate(moves: Vec<usize>) -> Self {
+        let mut distances = vec![u8::MAX; STATES];
+        let mut frontier = vec![0];
+        let mut depth = 0;
+        distances[0] = 0;
+
+        while !frontier.is_empty() {
+            depth += 1;
+            let mut next_frontier = vec![];
+
+            for state in frontier {
+                for &m in &moves {
+                    let next = apply(state, m);
+
+                    if distances[next] == u8::MAX {
+                        distances[next] = depth;
+                        next_frontier.push(next);
+                    }
+                }
+            }
+
+            frontier = next_frontier;
+        }
+
+        Self { moves, distances }
+    }
+}
+
+/// Applies a face turn to a state given by its coordinate.
+fn apply(state: usize, m: usize) -> usize {
+    let (permutations, twists) = &*MOVE_TABLES;
+    let (permutation, twist) = (state / 729, state % 729);
+
+    permutations[permutation * FACE_TURNS + m] as usize * 729 + twists[twist * FACE_TURNS + m] as usize
+}
+
+/// The permutation of the corners other than DBL, which are numbered 0 to 6.
+fn without_dbl(cp: &[u8; 8]) -> Vec<u8> {
+    cp.iter()
+        .enumerate()
+        .filter(|&(position, _)| position != DBL)
+        .map(|(_, &corner)| if corner as usize > DBL { corner - 1 } else { corner })
+        .collect()
+}
+
+/// The coordinate of a 2x2x2 cube with the DBL corner in place, made up of the
+/// permutation of the other corners and the twists of the first six corners.
+///
+/// Returns ``None`` if the cube is not a solvable 2x2x2.
+fn coordinate(cube: &impl Cube) -> Option<usize> {
+    if cube.size() != 2 {
+        return None;
+    }
+
+    let state = cube.state();
+
+    let color = |(face, index): (_, CubeSize)| state[S(2, face, corner_index_2x2(index)) as usize];
+
+    let mut corners = CubieCube::solved();
+
+    for (position, facelets) in CORNER_FACELETS.iter().enumerate() {
+        let (corner, twist) = (0..8)
+            .flat_map(|corner| (0..3).map(move |twist| (corner, twist)))
+            .find(|&(corner, twist)| {
+                (0..3).all(|n| CORNER_FACELETS[corner][n].0 == color(facelets[(n + twist) % 3]))
+            })?;
+
+        corners.cp[position] = corner as u8;
+        corners.co[position] = twist as u8;
+    }
+
+    let is_permutation = (0..8).all(|c| corners.cp.contains(&c));
+
+    if !is_permutation || corners.cp[DBL] != DBL as u8 || corners.co[DBL] != 0 || corners.co.iter().sum::<u8>() % 3 != 0 {
+        return None;
+    }
+
+    Some(permutation_rank(&without_dbl(&corners.cp)) * 729 + corners.twist() / 3)
+}
+
+/// The 2x2x2 cube with the given coordinate, the inverse of ``coordinate``.
+fn cube(state: usize) -> FaceletCube {
+    let mut corners = CubieCube::solved();
+
+    let mut permutation = permutation_unrank(state / 729, 7)
+        .into_iter()
+        .map(|corner| if corner as usize >= DBL { corner + 1 } else { corner })
+        .collect::<Vec<_>>();
+    permutation.insert(DBL, DBL as u8);
+    corners.cp.copy_from_slice(&permutation);
+
+    let mut twist = state % 729;
+    for position in (0..6).rev() {
+        corners.co[position] = (twist % 3) as u8;
+        twist /= 3;
+    }
+    corners.co[7] = (3 - corners.co[..7].iter().sum::<u8>() % 3) % 3;
+
+    let mut faces = vec![(Face::X, 0); 24];
+
+    for (position, facelets) in CORNER_FACELETS.iter().enumerate() {
+        let corner = CORNER_FACELETS[corners.cp[position] as usize];
+        let twist = corners.co[position] as usize;
+
+        for (n, &(face, index)) in corner.iter().enumerate() {
+            let (target_face, target_index) = facelets[(n + twist) % 3];
+            let solved = S(2, face, corner_index_2x2(index)) as u16;
+            faces[S(2, target_face, corner_index_2x2(target_index)) as usize] = (face, solved);
+        }
+    }
+
+    FaceletCube::from_tracked_faces(2, faces)
+}
@@ -0,0 +1,555 @@
+use rustc_hash::FxHashMap;
+
+use crate::generic_cube::{Cube, Face, Move, MoveVariant, CubeSize, ORDERED_FACES};
+use crate::generic_cube::{sticker_index as S};
+use crate::facelet_cube::FaceletCube;
+use crate::cubie_cube::permutation_parity;
+use crate::kociemba::{TwoPhaseOptions, solve_two_phase};
+use crate::pieces::{PieceType, piece_type, sticker_orbit, orbit_count, pieces};
+use crate::symmetry::face_move;
+
+/// Solves a 4x4x4 cube by reducing it to a 3x3x3.
+///
+/// The solution is made up of the following stages:
+/// 1. OLL parity, a single flipped edge on the reduced cube, is detected from the
+///    permutation of the wings and fixed with an inner slice turn.
+/// 2. PLL parity, two swapped edges on the reduced cube, is detected from the
+///    permutation of the corners and fixed with a face turn.
+/// 3. The centers are solved with commutators that cycle three center pieces.
+/// 4. The edges are paired with commutators that cycle three wings, which leaves
+///    every edge solved.
+/// 5. The remaining corners are solved as a 3x3x3 with ``solve_two_phase``.
+///
+/// Each parity is fixed first, as the commutators of the later stages cannot change
+/// it. The solution is written in WCA notation, where turns of the inner slices are
+/// made up of a wide turn and a turn of the outer face, such as ``Rw R'``. It may
+/// begin with whole cube rotations, and is typically a few hundred moves long.
+///
+/// Returns ``None`` if the cube is not a solvable 4x4x4.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, parse_scramble, solve_4x4};
+///
+/// let cube = FaceletCube::new(4).apply_moves(&parse_scramble(String::from("Rw U2 Fw' R D Lw2 Bw")));
+/// let solution = solve_4x4(&cube).unwrap();
+/// assert!(cube.apply_moves(&solution).is_solved());
+///
+/// assert_eq!(solve_4x4(&FaceletCube::new(3)), None);
+/// ```
+pub fn solve_4x4(cube: &impl Cube) -> Option<Vec<Move>> {
+    if cube.size() != 4 {
+        return None;
+    }
+
+    reduce(cube)
+}
+
+/// Solves a NxNxN cube of size 4 or more by reducing it to a 3x3x3.
+///
+/// The pieces are classified by ``piece_type`` and ``sticker_orbit``, and every orbit
+/// of centers and wings is solved separately with commutators that cycle three of
+/// its pieces. The stages are the same as for ``solve_4x4``:
+/// 1. The parity of each wing orbit is fixed with a turn of the inner slice it lies in.
+///    PLL parity is then fixed on even cubes, while on odd cubes the midges rule it out.
+/// 2. Each orbit of x-centers, t-centers and obliques is solved.
+/// 3. Each orbit of wings is paired with the midges, or solved on even cubes.
+/// 4. The reduced cube is solved as a 3x3x3 with ``solve_two_phase``.
+///
+/// Solutions grow to thousands of moves on big cubes, as every piece is cycled into
+/// place on its own. The time taken grows with the number of orbits, which is about
+/// a quarter of the square of the size.
+///
+/// Returns ``None`` if the cube is smaller than a 4x4x4, larger than a 10x10x10, or
+/// is not solvable. Smaller cubes can be solved with ``solve_2x2`` and ``solve_two_phase``.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, random_move_scramble, solve_nxn};
+///
+/// let cube = FaceletCube::new(5).apply_moves(&random_move_scramble(5, 60, 0));
+/// let solution = solve_nxn(&cube).unwrap();
+/// assert!(cube.apply_moves(&solution).is_solved());
+///
+/// assert_eq!(solve_nxn(&FaceletCube::new(3)), None);
+/// ```
+pub fn solve_nxn(cube: &impl Cube) -> Option<Vec<Move>> {
+    if !(4..=MAX_SIZE).contains(&cube.size()) {
+        return None;
+    }
+
+    reduce(cube)
+}
+
+/// The largest cube that the solver is tested on, which is solved in well under a
+/// second in release builds.
+const MAX_SIZE: CubeSize = 10;
+
+fn reduce(cube: &impl Cube) -> Option<Vec<Move>> {
+    let mut reduction = Reduction::new(cube)?;
+
+    reduction.fix_parity()?;
+    reduction.solve_centers()?;
+    reduction.pair_edges()?;
+    reduction.solve_3x3()?;
+
+    Some(reduction.solution)
+}
+
+/// A quarter or half turn of a single layer, counted from the outside of a face.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Turn {
+    face: Face,
+    layer: CubeSize,
+    variant: MoveVariant,
+}
+
+impl Turn {
+    /// The turn in WCA notation, where an inner layer is turned by turning it together
+    /// with the layers outside it, then turning those layers back.
+    fn moves(&self) -> Vec<Move> {
+        let wide = |layers| match layers {
+            1 => face_move(self.face, None, self.variant),
+            _ => face_move(self.face, Some(layers), self.variant),
+        };
+
+        match self.layer {
+            1 => vec![wide(1)],
+            layer => vec![wide(layer), wide(layer - 1).inverse()],
+        }
+    }
+}
+
+/// The state of a cube part way through a reduction, along with the moves made so far.
+struct Reduction {
+    size: CubeSize,
+    cube: FaceletCube,
+    solution: Vec<Move>,
+    /// Turns of every layer up to the middle of the cube.
+    turns: Vec<Turn>,
+    /// The position each sticker is taken to by each turn.
+    permutations: Vec<Vec<u16>>,
+    /// The piece type and orbit of each sticker.
+    orbits: Vec<(PieceType, CubeSize)>,
+    /// A 3-cycle of each orbit of centers and wings.
+    cycles: FxHashMap<(PieceType, CubeSize), Vec<usize>>,
+    /// The sticker each position of a wing or corner should hold once the cube is reduced.
+    targets: Vec<u16>,
+    /// The 3x3x3 solution that the wings of odd cubes are aimed at. Solving the centers
+    /// and wings leaves the corners and midges in place, so it still solves the cube.
+    solution_3x3: Option<Vec<Move>>,
+}
+
+impl Reduction {
+    /// Starts a reduction of a cube, rotating it into standard orientation.
+    ///
+    /// Returns ``None`` if the stickers do not make up each piece of the cube.
+    fn new(cube: &impl Cube) -> Option<Self> {
+        let size = cube.size();
+        let rotation = cube.orientation()?;
+        let tracked = FaceletCube::from_state(&cube.apply_moves(&rotation).state())?;
+
+        let turns = ORDERED_FACES
+            .iter()
+            .flat_map(|&face| (1..=(size + 1) / 2).map(move |layer| (face, layer)))
+            .flat_map(|(face, layer)| {
+                [MoveVariant::Standard, MoveVariant::Double, MoveVariant::Inverse]
+                    .iter()
+                    .map(move |&variant| Turn { face, layer, variant })
+            })
+            .collect::<Vec<_>>();
+
+        let permutations = turns
+            .iter()
+            .map(|turn| {
+                let mut permutation = vec![0; (6 * size * size) as usize];
+
+                for (position, &(_, sticker)) in FaceletCube::new(size).apply_moves(&turn.moves()).tracked_faces().iter().enumerate() {
+                    permutation[sticker as usize] = position as u16;
+                }
+
+                permutation
+            })
+            .collect();
+
+        let orbits = (0..6 * size * size).map(|i| (piece_type(size, i), sticker_orbit(size, i))).collect();
+
+        let targets = (0..6 * size * size).map(|i| i as u16).collect();
+        let mut reduction = Self { size, cube: tracked, solution: rotation, turns, permutations, orbits, cycles: FxHashMap::default(), targets, solution_3x3: None };
+        reduction.cycles = reduction.three_cycles();
+
+        Some(reduction)
+    }
+
+    /// Fixes the parity of every wing orbit, then the parity of the corners of even
+    /// cubes, so that the wings and corners can be solved with 3-cycles.
+    ///
+    /// The wings of odd cubes are paired with the midges rather than solved, so each
+    /// wing is aimed at the position taken to its own by a 3x3x3 solution of the
+    /// corners and midges. The midges then leave no room for PLL parity.
+    fn fix_parity(&mut self) -> Option<()> {
+        if self.size % 2 == 1 {
+            let solution = solve_two_phase(&self.reduced_3x3(), &TwoPhaseOptions::default())?;
+
+            for (position, &(_, sticker)) in FaceletCube::new(self.size).apply_moves(&solution).tracked_faces().iter().enumerate() {
+                self.targets[sticker as usize] = position as u16;
+            }
+
+            self.solution_3x3 = Some(solution);
+        }
+
+        for orbit in 0..orbit_count(self.size, PieceType::Wing) {
+            // A quarter turn of this inner slice is a 4-cycle of wings of the orbit.
+            if self.is_odd(PieceType::Wing, orbit)? {
+                self.apply(&[self.turn(Face::R, orbit + 2, MoveVariant::Standard)]);
+            }
+        }
+
+        if self.size % 2 == 0 && self.is_odd(PieceType::Corner, 0)? {
+            self.apply(&[self.turn(Face::U, 1, MoveVariant::Standard)]);
+        }
+
+        Some(())
+    }
+
+    /// Solves every center orbit, putting each center piece on the face of its colour.
+    fn solve_centers(&mut self) -> Option<()> {
+        let solved = FaceletCube::new(self.size);
+
+        for piece_type in [PieceType::XCenter, PieceType::TCenter, PieceType::Oblique] {
+            for orbit in 0..orbit_count(self.size, piece_type) {
+                let orbit = Orbit::new(self, &self.cycles[&(piece_type, orbit)]);
+                let targets = orbit.positions.iter().map(|&i| solved.tracked_faces()[i].0).collect::<Vec<_>>();
+
+                self.solve_orbit(&orbit, &targets, |cube, i| cube.tracked_faces()[i].0)?;
+            }
+        }
+
+        Some(())
+    }
+
+    /// Pairs the edges by putting every wing into its target position.
+    fn pair_edges(&mut self) -> Option<()> {
+        for orbit in 0..orbit_count(self.size, PieceType::Wing) {
+            let orbit = Orbit::new(self, &self.cycles[&(PieceType::Wing, orbit)]);
+            let targets = orbit.positions.iter().map(|&i| self.targets[i]).collect::<Vec<_>>();
+
+            self.solve_orbit(&orbit, &targets, |cube, i| cube.tracked_faces()[i].1)?;
+        }
+
+        Some(())
+    }
+
+    /// Solves the reduced cube as a 3x3x3, which only turns the outer layers. The
+    /// solution found by ``fix_parity`` on odd cubes is reused.
+    fn solve_3x3(&mut self) -> Option<()> {
+        let solution = match self.solution_3x3.take() {
+            Some(solution) => solution,
+            None => solve_two_phase(&self.reduced_3x3(), &TwoPhaseOptions::default())?,
+        };
+
+        for mv in solution {
+            self.cube = self.cube.apply_move(mv);
+            push_move(&mut self.solution, mv);
+        }
+
+        Some(())
+    }
+
+    /// The 3x3x3 made up of the corners, along with an edge piece and a center piece
+    /// of each face, which is the whole cube once it is reduced.
+    fn reduced_3x3(&self) -> FaceletCube {
+        let size = self.size;
+        let lines = [1, (size + 1) / 2, size];
+
+        let state = ORDERED_FACES
+            .iter()
+            .flat_map(|&face| (0..9).map(move |k| (face, (lines[k / 3] - 1) * size + lines[k % 3])))
+            .map(|(face, index)| self.cube.tracked_faces()[S(size, face, index) as usize].0)
+            .collect::<Vec<_>>();
+
+        FaceletCube::from(state)
+    }
+
+    /// Whether the pieces of an orbit are in an odd permutation relative to ``targets``.
+    fn is_odd(&self, piece_type: PieceType, orbit: CubeSize) -> Option<bool> {
+        let orbit_pieces = pieces(self.size)
+            .into_iter()
+            .map(|(_, stickers)| stickers)
+            .filter(|stickers| self.orbits[stickers[0] as usize] == (piece_type, orbit))
+            .collect::<Vec<_>>();
+
+        let piece_of = orbit_pieces
+            .iter()
+            .enumerate()
+            .flat_map(|(piece, stickers)| stickers.iter().map(move |&i| (i as u16, piece as u8)))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut target_positions = vec![0; self.targets.len()];
+
+        for (position, &target) in self.targets.iter().enumerate() {
+            target_positions[target as usize] = position as u16;
+        }
+
+        let permutation = orbit_pieces
+            .iter()
+            .map(|stickers| {
+                let sticker = self.cube.tracked_faces()[stickers[0] as usize].1;
+                piece_of.get(&target_positions[sticker as usize]).copied()
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(permutation_parity(&permutation))
+    }
+
+    /// Solves the pieces of an orbit with 3-cycles, given the label each position of the
+    /// orbit should end up with. Pieces with the same label can be used interchangeably.
+    ///
+    /// Returns ``None`` if the labels cannot be solved with 3-cycles.
+    fn solve_orbit<T: Copy + Eq>(&mut self, orbit: &Orbit, targets: &[T], label: impl Fn(&FaceletCube, usize) -> T) -> Option<()> {
+        loop {
+            let labels = orbit.positions.iter().map(|&i| label(&self.cube, i)).collect::<Vec<_>>();
+            let unsolved = (0..labels.len()).filter(|&p| labels[p] != targets[p]).collect::<Vec<_>>();
+
+            let to = match unsolved.first() {
+                Some(&to) => to,
+                None => return Some(()),
+            };
+
+            let from = unsolved.iter().copied().find(|&p| p != to && labels[p] == targets[to])?;
+
+            // With only two unsolved positions, the third piece is taken from a position
+            // holding a piece identical to the one that belongs in the first.
+            let third = unsolved
+                .iter()
+                .copied()
+                .find(|&p| p != from && p != to)
+                .or_else(|| (0..labels.len()).find(|&p| p != from && labels[p] == targets[p] && targets[p] == targets[from]))?;
+
+            let setup = orbit.setup(from, to, third);
+            let undo = setup.iter().rev().map(|&t| self.inverse(t)).collect::<Vec<_>>();
+
+            self.apply(&setup);
+            self.apply(&orbit.cycle);
+            self.apply(&undo);
+        }
+    }
+
+    /// Finds a commutator cycling three pieces of each orbit of centers and wings,
+    /// while leaving every other piece in place.
+    ///
+    /// The commutators have the form ``[A, X Y X']``, where ``A`` turns an inner layer,
+    /// ``X`` turns a face and ``Y`` turns any layer.
+    fn three_cycles(&self) -> FxHashMap<(PieceType, CubeSize), Vec<usize>> {
+        let wanted = [PieceType::XCenter, PieceType::TCenter, PieceType::Oblique, PieceType::Wing]
+            .iter()
+            .map(|&piece_type| orbit_count(self.size, piece_type) as usize)
+            .sum();
+
+        let quarter_turns = (0..self.turns.len()).filter(|&t| self.turns[t].variant != MoveVariant::Double).collect::<Vec<_>>();
+        let mut cycles = FxHashMap::default();
+
+        for &a in quarter_turns.iter().filter(|&&t| self.turns[t].layer > 1) {
+            for &x in quarter_turns.iter().filter(|&&t| self.turns[t].layer == 1) {
+                for &y in &quarter_turns {
+                    if cycles.len() == wanted {
+                        return cycles;
+                    }
+
+                    let (a_, x_, y_) = (self.inverse(a), self.inverse(x), self.inverse(y));
+                    let commutator = vec![a, x, y, x_, a_, x, y_, x_];
+                    let permutation = self.permutation(&commutator);
+
+                    let moved = (0..permutation.len()).filter(|&i| permutation[i] as usize != i).collect::<Vec<_>>();
+                    let orbit = match moved.first() {
+                        Some(&i) => self.orbits[i],
+                        None => continue,
+                    };
+
+                    let stickers_per_piece = match orbit.0 {
+                        PieceType::XCenter | PieceType::TCenter | PieceType::Oblique => 1,
+                        PieceType::Wing => 2,
+                        _ => continue,
+                    };
+
+                    if moved.len() == 3 * stickers_per_piece && moved.iter().all(|&i| self.orbits[i] == orbit) {
+                        cycles.entry(orbit).or_insert(commutator);
+                    }
+                }
+            }
+        }
+
+        cycles
+    }
+
+    /// The position each sticker is taken to by a sequence of turns.
+    fn permutation(&self, turns: &[usize]) -> Vec<u16> {
+        let mut permutation = (0..self.permutations[0].len() as u16).collect::<Vec<_>>();
+
+        for &t in turns {
+            for position in permutation.iter_mut() {
+                *position = self.permutations[t][*position as usize];
+            }
+        }
+
+        permutation
+    }
+
+    /// The turn of a layer, given by its index in ``turns``.
+    fn turn(&self, face: Face, layer: CubeSize, variant: MoveVariant) -> usize {
+        self.turns.iter().position(|&t| t == Turn { face, layer, variant }).unwrap()
+    }
+
+    /// The turn undoing a turn.
+    fn inverse(&self, turn: usize) -> usize {
+        let Turn { face, layer, variant } = self.turns[turn];
+
+        let variant = match variant {
+            MoveVariant::Standard => MoveVariant::Inverse,
+            MoveVariant::Double => MoveVariant::Double,
+            MoveVariant::Inverse => MoveVariant::Standard,
+        };
+
+        self.turn(face, layer, variant)
+    }
+
+    fn apply(&mut self, turns: &[usize]) {
+        for &t in turns {
+            for mv in self.turns[t].moves() {
+                self.cube = self.cube.apply_move(mv);
+                push_move(&mut self.solution, mv);
+            }
+        }
+    }
+}
+
+/// The stickers of one piece of each position in an orbit, along with a 3-cycle of the
+/// orbit and the setup moves that conjugate it into a cycle of any three positions.
+struct Orbit {
+    /// One sticker of each position, such that turns take these stickers to each other.
+    positions: Vec<usize>,
+    cycle: Vec<usize>,
+    /// The positions cycled by ``cycle``, as an index into ``next``.
+    goal: usize,
+    /// The next turn of the shortest setup taking each triple of positions to ``goal``.
+    next: Vec<u16>,
+    /// The position each turn takes each position to.
+    permutations: Vec<Vec<u8>>,
+}
+
+impl Orbit {
+    fn new(reduction: &Reduction, cycle: &[usize]) -> Self {
+        let permutation = reduction.permutation(cycle);
+        let first = (0..permutation.len()).find(|&i| permutation[i] as usize != i).unwrap();
+
+        // The stickers that turns take the first sticker of the cycle to.
+        let mut positions = vec![first];
+        let mut i = 0;
+
+        while i < positions.len() {
+            for turn in &reduction.permutations {
+                let next = turn[positions[i]] as usize;
+
+                if !positions.contains(&next) {
+                    positions.push(next);
+                }
+            }
+
+            i += 1;
+        }
+
+        positions.sort_unstable();
+
+        let local = |i: usize| positions.binary_search(&i).unwrap();
+        let permutations = reduction
+            .permutations
+            .iter()
+            .map(|turn| positions.iter().map(|&i| local(turn[i] as usize) as u8).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let n = positions.len();
+        let index = |a: usize, b: usize, c: usize| (a * n + b) * n + c;
+
+        let second = permutation[first] as usize;
+        let third = permutation[second] as usize;
+        let goal = index(local(first), local(second), local(third));
+
+        // Search backwards from the goal, so each triple records the turn towards it.
+        let mut inverses = vec![vec![0; n]; permutations.len()];
+
+        for (turn, permutation) in permutations.iter().enumerate() {
+            for (from, &to) in permutation.iter().enumerate() {
+                inverses[turn][to as usize] = from;
+            }
+        }
+
+        let mut next = vec![u16::MAX; n * n * n];
+        let mut seen = vec![false; n * n * n];
+        let mut frontier = vec![(local(first), local(second), local(third))];
+        seen[goal] = true;
+
+        while !frontier.is_empty() {
+            let mut next_frontier = vec![];
+
+            for (a, b, c) in frontier {
+                for (turn, inverse) in inverses.iter().enumerate() {
+                    let previous = (inverse[a], inverse[b], inverse[c]);
+                    let i = index(previous.0, previous.1, previous.2);
+
+                    if !seen[i] {
+                        seen[i] = true;
+                        next[i] = turn as u16;
+                        next_frontier.push(previous);
+                    }
+                }
+            }
+
+            frontier = next_frontier;
+        }
+
+        Self { positions, cycle: cycle.to_vec(), goal, next, permutations }
+    }
+
+    /// The turns taking three positions of the orbit, given by their index in
+    /// ``positions``, to the positions cycled by ``cycle``.
+    fn setup(&self, a: usize, b: usize, c: usize) -> Vec<usize> {
+        let n = self.positions.len();
+        let (mut a, mut b, mut c) = (a, b, c);
+        let mut turns = vec![];
+
+        while (a * n + b) * n + c != self.goal {
+            let turn = self.next[(a * n + b) * n + c] as usize;
+            let permutation = &self.permutations[turn];
+
+            turns.push(turn);
+            debug_assert!(turns.len() <= n * n * n, "setups are no longer than the number of triples");
+            a = permutation[a] as usize;
+            b = permutation[b] as usize;
+            c = permutation[c] as usize;
+        }
+
+        turns
+    }
+}
+
+/// Adds a move to a solution, merging it with the last move if they turn the same layers.
+fn push_move(moves: &mut Vec<Move>, mv: Move) {
+    match moves.last() {
+        Some(last) if last.with_variant(MoveVariant::Standard) == mv.with_variant(MoveVariant::Standard) => {
+            let turns = (last.get_variant() as u8 + mv.get_variant() as u8) % 4;
+            moves.pop();
+
+            match turns {
+                1 => moves.push(mv.with_variant(MoveVariant::Standard)),
+                2 => moves.push(mv.with_variant(MoveVariant::Double)),
+                3 => moves.push(mv.with_variant(MoveVariant::Inverse)),
+                _ => {}
+            }
+        }
+        _ => moves.push(mv),
+    }
+}
@@ -0,0 +1,262 @@
+use cgmath::{InnerSpace, Rotation, Vector3};
+
+use crate::generic_cube::{Cube, Move, CubeSize};
+use crate::geometric_cube::{GeoCube, Easing, StickerTransform, Timeline};
+use crate::geometric_cube::moves::GeometricMove;
+use crate::color_scheme::ColorScheme;
+
+use super::svg::{COS_30, MARGIN, STICKER_INSET, document, polygon};
+
+/// Options controlling how a sequence of moves is drawn as an animated SVG image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimationOptions {
+    /// The colours of the stickers. Masked stickers use the masked colour of the scheme.
+    pub scheme: ColorScheme,
+    /// The width of a sticker in pixels.
+    pub sticker_size: f64,
+    /// The time taken by each move in seconds.
+    pub move_duration: f64,
+    /// The time in seconds for which the cube is shown at rest before the first move and after the last move.
+    pub pause: f64,
+    /// The number of frames drawn for each move. More frames give a smoother animation and a larger image.
+    pub frames_per_move: u32,
+    /// The easing applied to each move.
+    pub easing: Easing,
+    /// Whether the move being played is written below the cube.
+    pub captions: bool,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            scheme: ColorScheme::default(),
+            sticker_size: 20.0,
+            move_duration: 0.5,
+            pause: 1.0,
+            frames_per_move: 8,
+            easing: Easing::default(),
+            captions: true,
+        }
+    }
+}
+
+/// Renders an animation of a sequence of moves being played on a cube in SVG format.
+///
+/// The cube is first shown at rest, after which each move is played in turn and the
+/// final cube is shown at rest. The animation then loops. It is drawn in the same
+/// isometric view as ``svg_isometric`` and uses SMIL animations, so the image is
+/// self-contained and plays in any modern browser without scripts.
+///
+/// # Examples
+///
+/// Playing the solution to a scramble:
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{GeoCube, AnimationOptions, parse_scramble, svg_animation};
+///
+/// let scramble = parse_scramble(String::from("R U R' U'"));
+/// let solution = parse_scramble(String::from("U R U' R'"));
+/// let cube = GeoCube::new(3).apply_moves(&scramble);
+///
+/// let svg = svg_animation(&cube, &solution, &AnimationOptions::default());
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains("dur=\"4s\""));
+/// ```
+pub fn svg_animation(cube: &GeoCube, moves: &[Move], options: &AnimationOptions) -> String {
+    let size = cube.size();
+    let s = options.sticker_size;
+    let frames_per_move = options.frames_per_move.max(1);
+
+    let mut timeline = Timeline::new(cube, moves);
+    timeline.easing = options.easing;
+    timeline.move_duration = options.move_duration;
+
+    let total = 2.0 * options.pause + timeline.duration();
+    let frame_duration = options.move_duration / frames_per_move as f64;
+
+    let mut body = String::new();
+    let mut add_frame = |start: f64, end: f64, frame: String| {
+        if end > start {
+            body.push_str(&format!("<g visibility=\"hidden\">\n{}{}</g>\n", visibility(start, end, total), frame));
+        }
+    };
+
+    let first_end = if moves.is_empty() { total } else { options.pause };
+    add_frame(0.0, first_end, draw_frame(size, &cube.sticker_transforms(), None, options));
+
+    for (i, &mv) in moves.iter().enumerate() {
+        for k in 0..frames_per_move {
+            let t = k as f64 / frames_per_move as f64;
+            let start = options.pause + i as f64 * options.move_duration + k as f64 * frame_duration;
+            let transforms = timeline.states()[i].animate_move(mv, t, options.easing);
+
+            add_frame(start, start + frame_duration, draw_frame(size, &transforms, Some((mv, options.easing.apply(t))), options));
+        }
+    }
+
+    if !moves.is_empty() {
+        add_frame(options.pause + timeline.duration(), total, draw_frame(size, &timeline.states().last().unwrap().sticker_transforms(), None, options));
+    }
+
+    if total <= 0.0 {
+        // There is nothing to animate, so the final cube is drawn at rest.
+        body = draw_frame(size, &timeline.states().last().unwrap().sticker_transforms(), None, options);
+    }
+
+    let radius = 3.0_f64.sqrt() * size as f64 / 2.0 * s + MARGIN;
+    let caption_height = if options.captions { 1.5 * s } else { 0.0 };
+
+    if options.captions && total > 0.0 {
+        for (i, mv) in moves.iter().enumerate() {
+            let start = options.pause + i as f64 * options.move_duration;
+
+            body.push_str(&format!(
+                "<text x=\"0\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" visibility=\"hidden\">{}{} ({}/{})</text>\n",
+                number(radius + s), number(s), visibility(start, start + options.move_duration, total), mv, i + 1, moves.len()
+            ));
+        }
+    }
+
+    document(-radius, -radius, 2.0 * radius, 2.0 * radius + caption_height, &body)
+}
+
+/// The polygons of a single frame, with each block of cubies turning together drawn as a
+/// convex box. The far block is drawn first, so that the near block covers it correctly.
+fn draw_frame(size: CubeSize, transforms: &[StickerTransform], turn: Option<(Move, f64)>, options: &AnimationOptions) -> String {
+    let n = size as f64;
+    let whole = [[-n, n]; 3];
+
+    let blocks = match turn {
+        None => vec![(whole, None, false)],
+        Some((mv, fraction)) => {
+            let rotation = GeometricMove::from(mv).get_partial_rotation_matrix(fraction);
+
+            match turning_layers(mv) {
+                Some((axis, sign, layers)) if layers < size => {
+                    let boundary = sign * (n - 2.0 * layers as f64);
+                    let (mut turning, mut fixed) = (whole, whole);
+
+                    if sign > 0.0 {
+                        turning[axis] = [boundary, n];
+                        fixed[axis] = [-n, boundary];
+                    } else {
+                        turning[axis] = [-n, boundary];
+                        fixed[axis] = [boundary, n];
+                    }
+
+                    // The view direction has a positive component along every axis.
+                    if sign > 0.0 {
+                        vec![(fixed, None, false), (turning, Some(rotation), true)]
+                    } else {
+                        vec![(turning, Some(rotation), true), (fixed, None, false)]
+                    }
+                }
+                _ => vec![(whole, Some(rotation), true)],
+            }
+        }
+    };
+
+    let mut frame = String::new();
+
+    for (bounds, rotation, turning) in blocks {
+        for (corners, normal) in box_faces(bounds) {
+            let (corners, normal) = match rotation {
+                Some(r) => (corners.map(|c| r.rotate_vector(c)), r.rotate_vector(normal)),
+                None => (corners, normal),
+            };
+
+            if facing_viewer(normal) {
+                frame.push_str(&polygon(&corners.map(project), options.sticker_size, "#000000"));
+            }
+        }
+
+        for sticker in transforms.iter().filter(|t| t.turning == turning) {
+            if facing_viewer(sticker.normal) {
+                let fill = options.scheme.color(sticker.face).hex();
+                frame.push_str(&polygon(&sticker.corners(STICKER_INSET).map(project), options.sticker_size, &fill));
+            }
+        }
+    }
+
+    frame
+}
+
+/// The axis of the layers turned by a move, the side of the cube they are on, and how
+/// many layers are turned. Returns ``None`` for rotations of the whole cube.
+fn turning_layers(mv: Move) -> Option<(usize, f64, CubeSize)> {
+    match mv {
+        Move::R(_) => Some((0, 1.0, 1)),
+        Move::L(_) => Some((0, -1.0, 1)),
+        Move::U(_) => Some((1, 1.0, 1)),
+        Move::D(_) => Some((1, -1.0, 1)),
+        Move::F(_) => Some((2, 1.0, 1)),
+        Move::B(_) => Some((2, -1.0, 1)),
+        Move::Rw(n, _) => Some((0, 1.0, n)),
+        Move::Lw(n, _) => Some((0, -1.0, n)),
+        Move::Uw(n, _) => Some((1, 1.0, n)),
+        Move::Dw(n, _) => Some((1, -1.0, n)),
+        Move::Fw(n, _) => Some((2, 1.0, n)),
+        Move::Bw(n, _) => Some((2, -1.0, n)),
+        Move::X(_) | Move::Y(_) | Move::Z(_) => None,
+    }
+}
+
+/// The faces of an axis aligned box, each with its outward normal.
+fn box_faces(bounds: [[f64; 2]; 3]) -> Vec<([Vector3<f64>; 4], Vector3<f64>)> {
+    let mut faces = vec![];
+
+    for axis in 0..3 {
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+
+        for (side, sign) in [(0, -1.0), (1, 1.0)] {
+            let corner = |a: usize, b: usize| {
+                let mut point = [0.0; 3];
+                point[axis] = bounds[axis][side];
+                point[u] = bounds[u][a];
+                point[v] = bounds[v][b];
+                Vector3::from(point)
+            };
+
+            let mut normal = [0.0; 3];
+            normal[axis] = sign;
+
+            faces.push(([corner(0, 0), corner(1, 0), corner(1, 1), corner(0, 1)], Vector3::from(normal)));
+        }
+    }
+
+    faces
+}
+
+fn facing_viewer(normal: Vector3<f64>) -> bool {
+    normal.dot(Vector3::new(1.0, 1.0, 1.0)) > 1e-6
+}
+
+/// Projects a point in ``GeoCube`` coordinates onto the isometric view, in units of a sticker.
+fn project(point: Vector3<f64>) -> (f64, f64) {
+    ((point.x - point.z) * COS_30 / 2.0, ((point.x + point.z) / 2.0 - point.y) / 2.0)
+}
+
+/// An animation showing an element between two times and hiding it otherwise.
+fn visibility(start: f64, end: f64, total: f64) -> String {
+    let (values, key_times) = match (start <= 0.0, end >= total) {
+        (true, true) => (String::from("visible"), String::from("0")),
+        (true, false) => (String::from("visible;hidden"), format!("0;{}", number(end / total))),
+        (false, true) => (String::from("hidden;visible"), format!("0;{}", number(start / total))),
+        (false, false) => (String::from("hidden;visible;hidden"), format!("0;{};{}", number(start / total), number(end / total))),
+    };
+
+    format!(
+        "<animate attributeName=\"visibility\" values=\"{}\" keyTimes=\"{}\" calcMode=\"discrete\" dur=\"{}s\" repeatCount=\"indefinite\"/>",
+        values, key_times, number(total)
+    )
+}
+
+/// Formats a time or length with at most six decimal places, as two decimal places
+/// are not precise enough for the key times of long animations.
+fn number(value: f64) -> String {
+    let formatted = format!("{:.6}", value);
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    if formatted == "-0" { String::from("0") } else { String::from(formatted) }
+}
@@ -0,0 +1,161 @@
+use crate::generic_cube::{Cube, Face, Move, CubeSize, sticker_index};
+use crate::facelet_cube::FaceletCube;
+use crate::cubie_cube::{CORNER_FACELETS, EDGE_FACELETS};
+use crate::scramble_parser::invert_moves;
+
+use super::svg::{Arrow, SvgOptions, arrows, document, num};
+
+/// The thickness of the side stickers as a fraction of the U face stickers.
+const SIDE_THICKNESS: f64 = 0.4;
+const GAP: f64 = 0.1;
+
+/// Renders a top-down view of the last layer of a 3x3x3 cube in SVG format.
+///
+/// The diagram shows the U face surrounded by the top row of each side face.
+/// If ``permutation_arrows`` is set, arrows are drawn on the U face from the solved
+/// position of each last layer piece to its current position, with a double headed
+/// arrow for each pair of swapped pieces. Arrows in the options are drawn as well.
+///
+/// # Panics
+///
+/// Panics if the cube is not a 3x3x3.
+///
+/// # Examples
+///
+/// The T permutation:
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, SvgOptions, parse_scramble, svg_last_layer};
+///
+/// let t_perm = parse_scramble(String::from("R U R' U' R' F R2 U' R' U' R U R' F'"));
+/// let svg = svg_last_layer(&FaceletCube::new(3).apply_moves(&t_perm), &SvgOptions::default(), true);
+/// assert_eq!(svg.matches("<line").count(), 2);
+/// ```
+pub fn svg_last_layer(cube: &FaceletCube, options: &SvgOptions, permutation_arrows: bool) -> String {
+    assert_eq!(cube.size(), 3, "last layer diagrams are only supported for 3x3x3 cubes");
+
+    let state = cube.state();
+    let s = options.sticker_size;
+    let t = s * SIDE_THICKNESS;
+    let g = s * GAP;
+    let offset = t + g;
+    let width = 3.0 * s + 2.0 * offset;
+
+    let mut centers = vec![None; state.len()];
+    let mut body = String::new();
+    let mut sticker = |i: CubeSize, x: f64, y: f64, w: f64, h: f64, centers: &mut Vec<Option<(f64, f64)>>| {
+        let i = i as usize;
+        body.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#000000\" stroke-width=\"{}\"/>\n",
+            num(x), num(y), num(w), num(h), options.scheme.color(state[i]).hex(), num(s * 0.08)
+        ));
+        centers[i] = Some((x + w / 2.0, y + h / 2.0));
+    };
+
+    for row in 0..3 {
+        for col in 0..3 {
+            let x = offset + col as f64 * s;
+            let y = offset + row as f64 * s;
+            sticker(sticker_index(3, Face::U, row * 3 + col + 1), x, y, s, s, &mut centers);
+        }
+    }
+
+    let mut side_centers = vec![None; state.len()];
+
+    for k in 0..3 {
+        let along = offset + k as f64 * s;
+        sticker(sticker_index(3, Face::B, 3 - k), along, 0.0, s, t, &mut side_centers);
+        sticker(sticker_index(3, Face::F, k + 1), along, offset + 3.0 * s + g, s, t, &mut side_centers);
+        sticker(sticker_index(3, Face::L, k + 1), 0.0, along, t, s, &mut side_centers);
+        sticker(sticker_index(3, Face::R, 3 - k), offset + 3.0 * s + g, along, t, s, &mut side_centers);
+    }
+
+    let mut all_arrows = if permutation_arrows { last_layer_arrows(cube) } else { vec![] };
+    all_arrows.extend(options.arrows.iter().copied());
+
+    body.push_str(&arrows(&all_arrows, &centers, s, &options.id_prefix));
+
+    document(0.0, 0.0, width, width, &body)
+}
+
+/// Renders the last layer case solved by an algorithm, in the same style as ``svg_last_layer``.
+///
+/// The case is obtained by applying the inverse of the algorithm to a solved cube.
+///
+/// # Examples
+///
+/// The U permutation:
+///
+/// ```rust
+/// use cubesim::{SvgOptions, parse_scramble, svg_last_layer_case};
+///
+/// let u_perm = parse_scramble(String::from("R U' R U R U R U' R' U' R2"));
+/// let svg = svg_last_layer_case(&u_perm, &SvgOptions::default(), true);
+/// assert_eq!(svg.matches("<line").count(), 3);
+/// ```
+pub fn svg_last_layer_case(algorithm: &[Move], options: &SvgOptions, permutation_arrows: bool) -> String {
+    let cube = FaceletCube::new(3).apply_moves(&invert_moves(algorithm));
+    svg_last_layer(&cube, options, permutation_arrows)
+}
+
+/// Arrows following the cycles of the last layer corners and edges of a 3x3x3 cube.
+///
+/// Each arrow points from the U facelet of the solved position of a piece to the U facelet
+/// of its current position. Pieces are recognised by their colours, relative to the
+/// centers, so cubes built from scanned colours get the same arrows. Pieces which have
+/// left the last layer or have masked stickers are ignored.
+pub fn last_layer_arrows(cube: &FaceletCube) -> Vec<Arrow> {
+    let state = cube.state();
+    let center = |face: Face| state[sticker_index(3, face, 5) as usize];
+
+    let corners = CORNER_FACELETS[..4].iter().map(|facelets| facelets.to_vec()).collect::<Vec<_>>();
+    let edges = EDGE_FACELETS[..4].iter().map(|facelets| facelets.to_vec()).collect::<Vec<_>>();
+
+    let mut result = vec![];
+
+    for facelets in [corners, edges] {
+        let pieces = facelets
+            .iter()
+            .map(|piece| piece.iter().map(|&(f, i)| sticker_index(3, f, i)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let solved_colors = facelets
+            .iter()
+            .map(|piece| piece.iter().map(|&(f, _)| center(f)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let moved_from = pieces
+            .iter()
+            .map(|piece| {
+                let colors = piece.iter().map(|&i| state[i as usize]).collect::<Vec<_>>();
+
+                solved_colors
+                    .iter()
+                    .position(|solved| !colors.contains(&Face::X) && solved.iter().all(|c| colors.contains(c)))
+            })
+            .collect::<Vec<_>>();
+
+        result.extend(cycle_arrows(&moved_from, &pieces));
+    }
+
+    result
+}
+
+fn cycle_arrows(moved_from: &[Option<usize>], pieces: &[Vec<CubeSize>]) -> Vec<Arrow> {
+    let mut arrows = vec![];
+
+    for (position, from) in moved_from.iter().enumerate() {
+        match *from {
+            Some(from) if from == position => {}
+            Some(from) if moved_from[from] == Some(position) && from < position => {
+                arrows.push(Arrow { from: pieces[from][0], to: pieces[position][0], double_headed: true });
+            }
+            Some(from) if moved_from[from] == Some(position) => {}
+            Some(from) => arrows.push(Arrow::new(pieces[from][0], pieces[position][0])),
+            None => {}
+        }
+    }
+
+    arrows
+}
@@ -0,0 +1,321 @@
+use rustc_hash::FxHashSet;
+
+use crate::generic_cube::{Cube, CubeSize};
+use crate::geometric_cube::GeoCube;
+use crate::color_scheme::{Color, ColorScheme};
+
+/// A cube exported in Wavefront OBJ format, together with its material library.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ObjMesh {
+    /// The contents of the ``.obj`` file.
+    pub obj: String,
+    /// The contents of the ``.mtl`` file, which must be saved under the name given on export.
+    pub mtl: String,
+}
+
+/// A flat quad with its corners listed counter-clockwise when viewed from the front.
+struct Quad {
+    corners: [[f64; 3]; 4],
+    normal: [f64; 3],
+}
+
+/// Quads grouped by the material they are drawn with.
+struct Mesh {
+    groups: Vec<(Color, Vec<Quad>)>,
+}
+
+/// The gap between neighbouring stickers, in units of a cubie.
+const STICKER_GAP: f64 = 0.1;
+/// How far stickers are raised above the cubie bodies, in units of a cubie.
+const STICKER_OFFSET: f64 = 0.01;
+
+impl Mesh {
+    /// Builds the cubie bodies and sticker quads of a cube.
+    ///
+    /// Each cubie is one unit wide and the cube is centered on the origin,
+    /// with the x, y and z axes pointing towards the R, U and F faces. Only the
+    /// faces of cubie bodies on the outside of the cube are built, as the others
+    /// are hidden by the neighbouring cubies.
+    fn new(cube: &GeoCube, scheme: &ColorScheme) -> Self {
+        let size = cube.size();
+        let body_color = Color::new("black", 'K', [0, 0, 0]);
+        let mut groups: Vec<(Color, Vec<Quad>)> = vec![(body_color, vec![])];
+        let mut cubies = vec![];
+        let mut seen = FxHashSet::default();
+
+        for (sticker, _) in &cube.stickers {
+            let position = [sticker.current.x, sticker.current.y, sticker.current.z];
+            let axis = position.iter().position(|c| c.abs() == size).unwrap();
+            let sign = position[axis].signum() as f64;
+
+            let mut center = position.map(|c| c as f64 * 0.5);
+            center[axis] += sign * STICKER_OFFSET;
+
+            let color = scheme.color(sticker.initial_face()).clone();
+            let quad = quad(center, axis, sign, 0.5 - STICKER_GAP / 2.0);
+
+            match groups.iter_mut().find(|(c, _)| *c == color) {
+                Some((_, quads)) => quads.push(quad),
+                None => groups.push((color, vec![quad])),
+            }
+
+            let mut cubie = position;
+            cubie[axis] -= position[axis].signum();
+            if seen.insert(cubie) {
+                cubies.push(cubie);
+            }
+        }
+
+        for cubie in cubies {
+            let center = cubie.map(|c| c as f64 * 0.5);
+
+            for axis in 0..3 {
+                for sign in [1.0, -1.0] {
+                    if cubie[axis] != sign as CubeSize * (size - 1) {
+                        continue;
+                    }
+
+                    let mut face_center = center;
+                    face_center[axis] += sign * 0.5;
+                    groups[0].1.push(quad(face_center, axis, sign, 0.5));
+                }
+            }
+        }
+
+        Self { groups }
+    }
+}
+
+/// A square facing along an axis, with the given distance from its center to its sides.
+fn quad(center: [f64; 3], axis: usize, sign: f64, half_width: f64) -> Quad {
+    let mut normal = [0.0; 3];
+    normal[axis] = sign;
+
+    let (u, v) = if sign > 0.0 { ((axis + 1) % 3, (axis + 2) % 3) } else { ((axis + 2) % 3, (axis + 1) % 3) };
+    let corner = |du: f64, dv: f64| {
+        let mut point = center;
+        point[u] += du * half_width;
+        point[v] += dv * half_width;
+        point
+    };
+
+    Quad {
+        corners: [corner(-1.0, -1.0), corner(1.0, -1.0), corner(1.0, 1.0), corner(-1.0, 1.0)],
+        normal,
+    }
+}
+
+/// Exports a cube as a mesh in Wavefront OBJ format.
+///
+/// The mesh contains the outside of a black body for every cubie and a coloured quad for
+/// every sticker, placed according to the current state of the cube. Each cubie is one unit
+/// wide and the cube is centered on the origin, with the y-axis pointing up. The OBJ file refers to its
+/// materials through the given material library name. Materials are named after the colours
+/// of the scheme, with whitespace and ``#`` replaced by underscores.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{GeoCube, ColorScheme, obj_mesh};
+///
+/// let mesh = obj_mesh(&GeoCube::new(3), &ColorScheme::default(), "cube.mtl");
+/// assert!(mesh.obj.contains("mtllib cube.mtl"));
+/// assert!(mesh.mtl.contains("newmtl white"));
+/// ```
+pub fn obj_mesh(cube: &GeoCube, scheme: &ColorScheme, material_library: &str) -> ObjMesh {
+    let mesh = Mesh::new(cube, scheme);
+
+    let mut obj = format!("# cubesim {0}x{0}x{0} cube\nmtllib {1}\n", cube.size(), material_library);
+    let mut mtl = String::from("# cubesim materials\n");
+    let mut faces = String::new();
+    let mut vertex_count = 0;
+    let mut normal_count = 0;
+
+    for (color, quads) in &mesh.groups {
+        let name = material_name(&color.name);
+
+        mtl.push_str(&format!(
+            "newmtl {}\nKd {} {} {}\n",
+            name, num(color.rgb[0] as f64 / 255.0), num(color.rgb[1] as f64 / 255.0), num(color.rgb[2] as f64 / 255.0)
+        ));
+
+        faces.push_str(&format!("usemtl {}\n", name));
+
+        for quad in quads {
+            for [x, y, z] in quad.corners {
+                obj.push_str(&format!("v {} {} {}\n", num(x), num(y), num(z)));
+            }
+
+            let [x, y, z] = quad.normal;
+            obj.push_str(&format!("vn {} {} {}\n", num(x), num(y), num(z)));
+            normal_count += 1;
+
+            faces.push_str(&format!(
+                "f {1}//{0} {2}//{0} {3}//{0} {4}//{0}\n",
+                normal_count, vertex_count + 1, vertex_count + 2, vertex_count + 3, vertex_count + 4
+            ));
+            vertex_count += 4;
+        }
+    }
+
+    obj.push_str(&faces);
+
+    ObjMesh { obj, mtl }
+}
+
+/// Exports a cube as a glTF 2.0 scene, with its geometry embedded as a base64 buffer.
+///
+/// The mesh is the same as for ``obj_mesh``, with one primitive for each material.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{GeoCube, ColorScheme, gltf_mesh};
+///
+/// let gltf = gltf_mesh(&GeoCube::new(3), &ColorScheme::default());
+/// assert!(gltf.contains("\"version\":\"2.0\""));
+/// ```
+pub fn gltf_mesh(cube: &GeoCube, scheme: &ColorScheme) -> String {
+    let mesh = Mesh::new(cube, scheme);
+
+    let mut buffer: Vec<u8> = vec![];
+    let mut buffer_views = vec![];
+    let mut accessors = vec![];
+    let mut primitives = vec![];
+    let mut materials = vec![];
+
+    let mut add_view = |buffer: &mut Vec<u8>, data: Vec<u8>, target: u32| {
+        buffer_views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{}}}",
+            buffer.len(), data.len(), target
+        ));
+        buffer.extend(data);
+        buffer_views.len() - 1
+    };
+
+    for (material, (color, quads)) in mesh.groups.iter().enumerate() {
+        let positions = quads.iter().flat_map(|q| q.corners.iter().flatten().copied()).collect::<Vec<_>>();
+        let normals = quads.iter().flat_map(|q| [q.normal; 4].concat()).collect::<Vec<_>>();
+        let indices = (0..quads.len() as u32)
+            .flat_map(|q| [0, 1, 2, 0, 2, 3].map(|k| 4 * q + k))
+            .collect::<Vec<_>>();
+
+        let min = (0..3).map(|k| positions.iter().skip(k).step_by(3).copied().fold(f64::INFINITY, f64::min)).collect::<Vec<_>>();
+        let max = (0..3).map(|k| positions.iter().skip(k).step_by(3).copied().fold(f64::NEG_INFINITY, f64::max)).collect::<Vec<_>>();
+
+        let position_view = add_view(&mut buffer, positions.iter().flat_map(|&p| (p as f32).to_le_bytes()).collect(), 34962);
+        let normal_view = add_view(&mut buffer, normals.iter().flat_map(|&n| (n as f32).to_le_bytes()).collect(), 34962);
+        let index_view = add_view(&mut buffer, indices.iter().flat_map(|i| i.to_le_bytes()).collect(), 34963);
+
+        accessors.push(format!(
+            "{{\"bufferView\":{},\"componentType\":5126,\"count\":{},\"type\":\"VEC3\",\"min\":[{}],\"max\":[{}]}}",
+            position_view, 4 * quads.len(),
+            min.iter().map(|&v| (v as f32).to_string()).collect::<Vec<_>>().join(","),
+            max.iter().map(|&v| (v as f32).to_string()).collect::<Vec<_>>().join(",")
+        ));
+        accessors.push(format!(
+            "{{\"bufferView\":{},\"componentType\":5126,\"count\":{},\"type\":\"VEC3\"}}",
+            normal_view, 4 * quads.len()
+        ));
+        accessors.push(format!(
+            "{{\"bufferView\":{},\"componentType\":5125,\"count\":{},\"type\":\"SCALAR\"}}",
+            index_view, indices.len()
+        ));
+
+        let accessor = accessors.len() - 3;
+        primitives.push(format!(
+            "{{\"attributes\":{{\"POSITION\":{},\"NORMAL\":{}}},\"indices\":{},\"material\":{}}}",
+            accessor, accessor + 1, accessor + 2, material
+        ));
+
+        let [r, g, b] = color.rgb.map(srgb_to_linear);
+        materials.push(format!(
+            "{{\"name\":{},\"pbrMetallicRoughness\":{{\"baseColorFactor\":[{},{},{},1],\"metallicFactor\":0,\"roughnessFactor\":0.5}}}}",
+            json_string(&color.name), num(r), num(g), num(b)
+        ));
+    }
+
+    format!(
+        concat!(
+            "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"cubesim\"}},",
+            "\"scene\":0,\"scenes\":[{{\"nodes\":[0]}}],\"nodes\":[{{\"mesh\":0}}],",
+            "\"meshes\":[{{\"primitives\":[{}]}}],",
+            "\"materials\":[{}],",
+            "\"buffers\":[{{\"byteLength\":{},\"uri\":\"data:application/octet-stream;base64,{}\"}}],",
+            "\"bufferViews\":[{}],",
+            "\"accessors\":[{}]}}\n"
+        ),
+        primitives.join(","),
+        materials.join(","),
+        buffer.len(),
+        base64(&buffer),
+        buffer_views.join(","),
+        accessors.join(",")
+    )
+}
+
+/// Turns a colour name into a material name for OBJ and MTL files, which must be a single
+/// token. Whitespace, control characters and comment markers are replaced by underscores.
+fn material_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_whitespace() || c.is_control() || c == '#' { '_' } else { c })
+        .collect();
+
+    if name.is_empty() { String::from("_") } else { name }
+}
+
+/// Quotes a string as a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+fn srgb_to_linear(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let triple = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+
+        for k in 0..4 {
+            if k <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - 6 * k) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Formats a coordinate with at most four decimal places, so that exports are reproducible.
+fn num(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    if formatted == "-0" { String::from("0") } else { String::from(formatted) }
+}
@@ -0,0 +1,38 @@
+mod animation;
+mod last_layer;
+mod mesh;
+mod png;
+mod svg;
+mod terminal;
+
+pub use animation::{AnimationOptions, svg_animation};
+pub use last_layer::{svg_last_layer, svg_last_layer_case, last_layer_arrows};
+pub use mesh::{ObjMesh, obj_mesh, gltf_mesh};
+pub use png::{PngOptions, png_net, png_isometric};
+pub use svg::{Arrow, SvgOptions, svg_net, svg_isometric};
+pub use terminal::terminal_net;
+
+use crate::generic_cube::{Face, CubeSize, sticker_index};
+
+/// The position of every sticker in the standard cross shaped net of a cube.
+///
+/// The net is ``3 * size`` rows by ``4 * size`` columns, with the U face above
+/// the F face, the L, F, R and B faces in a row, and the D face below the F face.
+/// Each entry is the index of the sticker drawn at that position, if any.
+pub(crate) fn net_grid(size: CubeSize) -> Vec<Vec<Option<usize>>> {
+    let size = size as usize;
+    let mut grid = vec![vec![None; 4 * size]; 3 * size];
+
+    let faces = [(Face::U, 0, 1), (Face::L, 1, 0), (Face::F, 1, 1), (Face::R, 1, 2), (Face::B, 1, 3), (Face::D, 2, 1)];
+
+    for (face, face_row, face_col) in faces {
+        for row in 0..size {
+            for col in 0..size {
+                let index = sticker_index(size as CubeSize, face, (row * size + col + 1) as CubeSize);
+                grid[face_row * size + row][face_col * size + col] = Some(index as usize);
+            }
+        }
+    }
+
+    grid
+}
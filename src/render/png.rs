@@ -0,0 +1,334 @@
+use crate::generic_cube::{Cube, Face, sticker_index};
+use crate::color_scheme::ColorScheme;
+
+use super::net_grid;
+use super::svg::{COS_30, MARGIN, STICKER_INSET, isometric_quad};
+
+/// Options controlling how a cube is drawn as a PNG image.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PngOptions {
+    /// The colours of the stickers. Masked stickers use the masked colour of the scheme.
+    pub scheme: ColorScheme,
+    /// The width of a sticker in pixels.
+    pub sticker_size: u32,
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self {
+            scheme: ColorScheme::default(),
+            sticker_size: 20,
+        }
+    }
+}
+
+const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+/// Renders a cube as an unfolded net in PNG format.
+///
+/// The net has the same layout as ``terminal_net``. The background is transparent,
+/// and the same cube and options always produce the same bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, PngOptions, png_net};
+///
+/// let png = png_net(&FaceletCube::new(3), &PngOptions::default());
+/// assert_eq!(&png[1..4], b"PNG");
+/// ```
+pub fn png_net(cube: &impl Cube, options: &PngOptions) -> Vec<u8> {
+    let size = cube.size() as u32;
+    let state = cube.state();
+    let s = options.sticker_size;
+    let margin = MARGIN as u32;
+    let border = ((s as f64 * STICKER_INSET).round() as u32).max(1);
+
+    let mut canvas = Canvas::new(4 * size * s + 2 * margin, 3 * size * s + 2 * margin);
+
+    for (row, cells) in net_grid(cube.size()).iter().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            if let Some(i) = cell {
+                let x = margin + col as u32 * s;
+                let y = margin + row as u32 * s;
+                let [r, g, b] = options.scheme.color(state[*i]).rgb;
+
+                canvas.fill_rect(x, y, s, s, BLACK);
+                canvas.fill_rect(x + border, y + border, s.saturating_sub(2 * border), s.saturating_sub(2 * border), [r, g, b, 255]);
+            }
+        }
+    }
+
+    canvas.encode()
+}
+
+/// Renders an isometric view of a cube in PNG format, showing the U, F and R faces.
+///
+/// The view matches ``svg_isometric``. The background is transparent, and the same
+/// cube and options always produce the same bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, PngOptions, png_isometric};
+///
+/// let png = png_isometric(&FaceletCube::new(3), &PngOptions::default());
+/// assert_eq!(&png[1..4], b"PNG");
+/// ```
+pub fn png_isometric(cube: &impl Cube, options: &PngOptions) -> Vec<u8> {
+    let size = cube.size();
+    let state = cube.state();
+    let s = options.sticker_size as f64;
+    let n = size as f64;
+
+    let half_width = n * s * COS_30 + MARGIN;
+    let half_height = n * s + MARGIN;
+    let mut canvas = Canvas::new((2.0 * half_width).ceil() as u32, (2.0 * half_height).ceil() as u32);
+
+    let to_pixels = |points: Vec<(f64, f64)>| points
+        .into_iter()
+        .map(|(x, y)| (x * s + half_width, y * s + half_height))
+        .collect::<Vec<_>>();
+
+    for face in [Face::U, Face::F, Face::R] {
+        canvas.fill_polygon(&to_pixels(isometric_quad(size, face, 0.0, 0.0, n, n, 0.0)), BLACK);
+    }
+
+    for face in [Face::U, Face::F, Face::R] {
+        for row in 0..size {
+            for col in 0..size {
+                let i = sticker_index(size, face, row * size + col + 1) as usize;
+                let quad = isometric_quad(size, face, row as f64, col as f64, row as f64 + 1.0, col as f64 + 1.0, STICKER_INSET);
+                let [r, g, b] = options.scheme.color(state[i]).rgb;
+
+                canvas.fill_polygon(&to_pixels(quad), [r, g, b, 255]);
+            }
+        }
+    }
+
+    canvas.encode()
+}
+
+/// An RGBA image with a transparent background.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self { width, height, pixels: vec![[0, 0, 0, 0]; (width * height) as usize] }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 4]) {
+        for py in y..(y + height).min(self.height) {
+            for px in x..(x + width).min(self.width) {
+                self.pixels[(py * self.width + px) as usize] = color;
+            }
+        }
+    }
+
+    /// Fills a convex polygon, colouring each pixel whose center lies inside it.
+    fn fill_polygon(&mut self, points: &[(f64, f64)], color: [u8; 4]) {
+        let min_x = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min).floor().max(0.0) as u32;
+        let max_x = points.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max).ceil().min(self.width as f64) as u32;
+        let min_y = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min).floor().max(0.0) as u32;
+        let max_y = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max).ceil().min(self.height as f64) as u32;
+
+        for py in min_y..max_y {
+            for px in min_x..max_x {
+                let (cx, cy) = (px as f64 + 0.5, py as f64 + 0.5);
+
+                let crosses = (0..points.len()).map(|k| {
+                    let (x1, y1) = points[k];
+                    let (x2, y2) = points[(k + 1) % points.len()];
+                    (x2 - x1) * (cy - y1) - (y2 - y1) * (cx - x1)
+                }).collect::<Vec<_>>();
+
+                if crosses.iter().all(|&c| c >= 0.0) || crosses.iter().all(|&c| c <= 0.0) {
+                    self.pixels[(py * self.width + px) as usize] = color;
+                }
+            }
+        }
+    }
+
+    /// Encodes the image as a PNG file.
+    fn encode(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(((4 * self.width + 1) * self.height) as usize);
+
+        for row in self.pixels.chunks(self.width.max(1) as usize) {
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+
+        let mut header = vec![];
+        header.extend(self.width.to_be_bytes());
+        header.extend(self.height.to_be_bytes());
+        header.extend([8, 6, 0, 0, 0]);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib(&raw, 4 * self.width as usize + 1));
+        write_chunk(&mut png, b"IEND", &[]);
+
+        png
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(&[&kind[..], data].concat()).to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+/// Compresses data into a zlib stream using a single fixed Huffman block.
+///
+/// Rendered cubes consist of large flat areas, so back references are only searched
+/// for at the distance of the previous pixel and of the previous row.
+fn zlib(data: &[u8], row_length: usize) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    bits.write(1, 1);
+    bits.write(1, 2);
+
+    let distances = [4, row_length]
+        .iter()
+        .copied()
+        .filter(|&d| d <= 32768)
+        .collect::<Vec<_>>();
+
+    let mut i = 0;
+    while i < data.len() {
+        let best = distances
+            .iter()
+            .filter(|&&d| d <= i)
+            .map(|&d| {
+                let length = (0..258.min(data.len() - i))
+                    .take_while(|&k| data[i + k] == data[i + k - d])
+                    .count();
+                (length, d)
+            })
+            .max_by_key(|&(length, _)| length);
+
+        match best {
+            Some((length, distance)) if length >= 3 => {
+                write_length(&mut bits, length);
+                write_distance(&mut bits, distance);
+                i += length;
+            }
+            _ => {
+                write_literal(&mut bits, data[i] as u32);
+                i += 1;
+            }
+        }
+    }
+
+    write_literal(&mut bits, 256);
+
+    let mut stream = vec![0x78, 0x01];
+    stream.extend(bits.finish());
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+const LENGTH_BASES: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+fn write_literal(bits: &mut BitWriter, symbol: u32) {
+    match symbol {
+        0..=143 => bits.write_huffman(0x30 + symbol, 8),
+        144..=255 => bits.write_huffman(0x190 + symbol - 144, 9),
+        256..=279 => bits.write_huffman(symbol - 256, 7),
+        _ => bits.write_huffman(0xc0 + symbol - 280, 8),
+    }
+}
+
+fn write_length(bits: &mut BitWriter, length: usize) {
+    let code = LENGTH_BASES.iter().rposition(|&base| base <= length).unwrap();
+    write_literal(bits, 257 + code as u32);
+    bits.write((length - LENGTH_BASES[code]) as u32, LENGTH_EXTRA_BITS[code]);
+}
+
+fn write_distance(bits: &mut BitWriter, distance: usize) {
+    let code = DISTANCE_BASES.iter().rposition(|&base| base <= distance).unwrap();
+    bits.write_huffman(code as u32, 5);
+    bits.write((distance - DISTANCE_BASES[code]) as u32, DISTANCE_EXTRA_BITS[code]);
+}
+
+/// Packs bits into bytes starting from the least significant bit, as required by deflate.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, count: u32) {
+        for k in 0..count {
+            self.buffer |= ((value >> k) & 1) << self.count;
+            self.count += 1;
+
+            if self.count == 8 {
+                self.bytes.push(self.buffer as u8);
+                self.buffer = 0;
+                self.count = 0;
+            }
+        }
+    }
+
+    /// Huffman codes are packed starting from their most significant bit.
+    fn write_huffman(&mut self, code: u32, length: u32) {
+        for k in (0..length).rev() {
+            self.write((code >> k) & 1, 1);
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
@@ -0,0 +1,240 @@
+use crate::generic_cube::{Cube, Face, CubeSize, sticker_index};
+use crate::color_scheme::ColorScheme;
+
+use super::net_grid;
+
+/// An arrow drawn between the centers of two stickers.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Arrow {
+    /// The index of the sticker the arrow starts from.
+    pub from: CubeSize,
+    /// The index of the sticker the arrow points to.
+    pub to: CubeSize,
+    /// Whether the arrow also points back to its starting sticker.
+    pub double_headed: bool,
+}
+
+impl Arrow {
+    /// Creates an arrow pointing from one sticker to another.
+    pub fn new(from: CubeSize, to: CubeSize) -> Self {
+        Self { from, to, double_headed: false }
+    }
+}
+
+/// Options controlling how a cube is drawn as an SVG image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// The colours of the stickers. Masked stickers use the masked colour of the scheme.
+    pub scheme: ColorScheme,
+    /// The width of a sticker in pixels.
+    pub sticker_size: f64,
+    /// Arrows drawn on top of the stickers. Arrows between stickers which are
+    /// not visible in the drawn view are skipped.
+    pub arrows: Vec<Arrow>,
+    /// Prefix for the ids of elements defined in the image, such as the arrowhead
+    /// marker. Images inlined into the same HTML page need distinct prefixes.
+    pub id_prefix: String,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            scheme: ColorScheme::default(),
+            sticker_size: 20.0,
+            arrows: vec![],
+            id_prefix: String::from("cubesim-"),
+        }
+    }
+}
+
+pub(crate) const MARGIN: f64 = 4.0;
+pub(crate) const STICKER_INSET: f64 = 0.08;
+
+/// Renders a cube as an unfolded net in SVG format.
+///
+/// The net has the same layout as ``terminal_net``.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, SvgOptions, svg_net};
+///
+/// let svg = svg_net(&FaceletCube::new(3), &SvgOptions::default());
+/// assert!(svg.starts_with("<svg"));
+/// assert_eq!(svg.matches("<rect").count(), 54);
+/// ```
+pub fn svg_net(cube: &impl Cube, options: &SvgOptions) -> String {
+    let size = cube.size();
+    let state = cube.state();
+    let s = options.sticker_size;
+
+    let width = 4.0 * size as f64 * s + 2.0 * MARGIN;
+    let height = 3.0 * size as f64 * s + 2.0 * MARGIN;
+
+    let mut centers = vec![None; state.len()];
+    let mut body = String::new();
+
+    for (row, cells) in net_grid(size).iter().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            if let Some(i) = cell {
+                let x = MARGIN + col as f64 * s;
+                let y = MARGIN + row as f64 * s;
+
+                body.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#000000\" stroke-width=\"{}\"/>\n",
+                    num(x), num(y), num(s), num(s), options.scheme.color(state[*i]).hex(), num(s * STICKER_INSET)
+                ));
+                centers[*i] = Some((x + s / 2.0, y + s / 2.0));
+            }
+        }
+    }
+
+    body.push_str(&arrows(&options.arrows, &centers, s, &options.id_prefix));
+
+    document(0.0, 0.0, width, height, &body)
+}
+
+/// Renders an isometric view of a cube in SVG format, showing the U, F and R faces.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, SvgOptions, svg_isometric};
+///
+/// let svg = svg_isometric(&FaceletCube::new(3), &SvgOptions::default());
+/// assert!(svg.starts_with("<svg"));
+/// assert_eq!(svg.matches("<polygon").count(), 3 + 27);
+/// ```
+pub fn svg_isometric(cube: &impl Cube, options: &SvgOptions) -> String {
+    let size = cube.size();
+    let state = cube.state();
+    let s = options.sticker_size;
+    let n = size as f64;
+
+    let mut centers = vec![None; state.len()];
+    let mut body = String::new();
+
+    for face in [Face::U, Face::F, Face::R] {
+        let outline = isometric_quad(size, face, 0.0, 0.0, n, n, 0.0);
+        body.push_str(&polygon(&outline, s, "#000000"));
+    }
+
+    for face in [Face::U, Face::F, Face::R] {
+        for row in 0..size {
+            for col in 0..size {
+                let i = sticker_index(size, face, row * size + col + 1) as usize;
+                let quad = isometric_quad(size, face, row as f64, col as f64, row as f64 + 1.0, col as f64 + 1.0, STICKER_INSET);
+
+                body.push_str(&polygon(&quad, s, &options.scheme.color(state[i]).hex()));
+                centers[i] = Some(scale(center(&quad), s));
+            }
+        }
+    }
+
+    body.push_str(&arrows(&options.arrows, &centers, s, &options.id_prefix));
+
+    let half_width = n * s * COS_30 + MARGIN;
+    let half_height = n * s + MARGIN;
+
+    document(-half_width, -half_height, 2.0 * half_width, 2.0 * half_height, &body)
+}
+
+pub(crate) const COS_30: f64 = 0.866_025_403_784_438_6;
+
+/// Projects a point in cube space, where each coordinate is in ``[0, size]``,
+/// onto the isometric view with the y-axis pointing up.
+pub(crate) fn isometric_projection(x: f64, y: f64, z: f64) -> (f64, f64) {
+    ((x - z) * COS_30, (x + z) * 0.5 - y)
+}
+
+/// The projected corners of the region between two rows and columns of a face,
+/// shrunk towards its center by an inset given as a fraction of a sticker.
+pub(crate) fn isometric_quad(size: CubeSize, face: Face, row1: f64, col1: f64, row2: f64, col2: f64, inset: f64) -> Vec<(f64, f64)> {
+    let n = size as f64;
+    let (row1, col1, row2, col2) = (row1 + inset, col1 + inset, row2 - inset, col2 - inset);
+
+    let point = |row: f64, col: f64| match face {
+        Face::U => isometric_projection(col, n, row),
+        Face::F => isometric_projection(col, n - row, n),
+        Face::R => isometric_projection(n, n - row, n - col),
+        _ => unreachable!(),
+    };
+
+    vec![point(row1, col1), point(row1, col2), point(row2, col2), point(row2, col1)]
+}
+
+fn center(points: &[(f64, f64)]) -> (f64, f64) {
+    let count = points.len() as f64;
+    let (x, y) = points.iter().fold((0.0, 0.0), |(x, y), (px, py)| (x + px, y + py));
+    (x / count, y / count)
+}
+
+fn scale((x, y): (f64, f64), s: f64) -> (f64, f64) {
+    (x * s, y * s)
+}
+
+pub(crate) fn polygon(points: &[(f64, f64)], s: f64, fill: &str) -> String {
+    let points = points
+        .iter()
+        .map(|&p| {
+            let (x, y) = scale(p, s);
+            format!("{},{}", num(x), num(y))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!("<polygon points=\"{}\" fill=\"{}\"/>\n", points, fill)
+}
+
+/// Draws the arrows between visible stickers, preceded by the definition of their
+/// arrowhead marker if any arrow is drawn.
+pub(crate) fn arrows(arrows: &[Arrow], centers: &[Option<(f64, f64)>], s: f64, id_prefix: &str) -> String {
+    let mut body = String::new();
+    let center = |i: CubeSize| centers.get(i as usize).copied().flatten();
+
+    for arrow in arrows {
+        if let (Some((x1, y1)), Some((x2, y2))) = (center(arrow.from), center(arrow.to)) {
+            let start = if arrow.double_headed { format!(" marker-start=\"url(#{}arrowhead)\"", id_prefix) } else { String::new() };
+
+            body.push_str(&format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#000000\" stroke-width=\"{}\"{} marker-end=\"url(#{}arrowhead)\"/>\n",
+                num(x1), num(y1), num(x2), num(y2), num(s * 0.1), start, id_prefix
+            ));
+        }
+    }
+
+    if body.is_empty() {
+        return body;
+    }
+
+    format!(
+        concat!(
+            "<defs><marker id=\"{}arrowhead\" viewBox=\"0 0 10 10\" refX=\"8\" refY=\"5\" markerWidth=\"4\" markerHeight=\"4\" orient=\"auto-start-reverse\">",
+            "<path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#000000\"/></marker></defs>\n",
+            "{}"
+        ),
+        id_prefix, body
+    )
+}
+
+/// Wraps the body of an SVG image in the document element.
+pub(crate) fn document(x: f64, y: f64, width: f64, height: f64, body: &str) -> String {
+    format!(
+        concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">\n",
+            "{}",
+            "</svg>\n"
+        ),
+        num(width), num(height), num(x), num(y), num(width), num(height), body
+    )
+}
+
+/// Formats a coordinate with at most two decimal places, so that images are reproducible.
+pub(crate) fn num(value: f64) -> String {
+    let formatted = format!("{:.2}", value);
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    if formatted == "-0" { String::from("0") } else { String::from(formatted) }
+}
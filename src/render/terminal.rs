@@ -0,0 +1,51 @@
+use crate::generic_cube::Cube;
+use crate::color_scheme::ColorScheme;
+
+use super::net_grid;
+
+/// Renders a cube as an unfolded net for printing to a terminal.
+///
+/// Without a colour scheme, each sticker is drawn as the letter of its face. With a
+/// colour scheme, each sticker is drawn as a block of its colour using ANSI true colour
+/// escape codes.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant};
+/// use cubesim::{FaceletCube, terminal_net};
+///
+/// let cube = FaceletCube::new(2).apply_move(Move::U(MoveVariant::Standard));
+/// assert_eq!(terminal_net(&cube, None), [
+///     "    U U",
+///     "    U U",
+///     "F F R R B B L L",
+///     "L L F F R R B B",
+///     "    D D",
+///     "    D D",
+/// ].join("\n") + "\n");
+/// ```
+pub fn terminal_net(cube: &impl Cube, scheme: Option<&ColorScheme>) -> String {
+    let state = cube.state();
+    let mut net = String::new();
+
+    for row in net_grid(cube.size()) {
+        let mut line = String::new();
+
+        for sticker in row {
+            match (sticker, scheme) {
+                (None, _) => line.push_str("  "),
+                (Some(i), None) => line.push_str(&format!("{} ", state[i])),
+                (Some(i), Some(scheme)) => {
+                    let [r, g, b] = scheme.color(state[i]).rgb;
+                    line.push_str(&format!("\x1b[48;2;{};{};{}m  \x1b[0m", r, g, b));
+                }
+            }
+        }
+
+        net.push_str(line.trim_end());
+        net.push('\n');
+    }
+
+    net
+}
@@ -3,65 +3,63 @@ use crate::generic_cube::Move::*;
 use crate::generic_cube::MoveVariant::*;
 
 /// Converts a WCA Notation scramble into ``Vec<Move>``.
+///
+/// A number in front of a wide move is its layer count, so ``2Lw`` is a single
+/// quarter turn of two layers and ``2Lw2`` is the double turn.
+///
+/// # Panics
+///
+/// Panics if any move of the scramble is not valid WCA notation.
 pub fn parse_scramble(scramble: String) -> Vec<Move> {
-    scramble.trim().split_whitespace().map(convert_move).collect()
+    scramble
+        .split_whitespace()
+        .map(|mv| parse_move(mv).unwrap_or_else(|| panic!("invalid move notation \"{}\"", mv)))
+        .collect()
 }
 
-fn convert_move(mv: &str) -> Move {
-    let slice = get_slice(mv);
-    let variant = get_variant(mv);
+/// Parses a single move in WCA notation, such as ``R'``, ``Uw2`` or ``3Fw``.
+/// Unlike ``parse_scramble``, invalid notation is rejected by returning ``None``.
+pub(crate) fn parse_move(mv: &str) -> Option<Move> {
+    let digits = mv.chars().take_while(|c| c.is_ascii_digit()).count();
+    let mut chars = mv[digits..].chars();
+    let face = chars.next()?;
+    let rest = chars.as_str();
 
-    if !mv.contains('w') {
-        match &mv[0..1] {
-            "U" => U(variant),
-            "R" => R(variant),
-            "F" => F(variant),
-            "L" => L(variant),
-            "D" => D(variant),
-            "B" => B(variant),
-            "x" => X(variant),
-            "y" => Y(variant),
-            "z" => Z(variant),
-            _ => panic!()
-        }    
-    } else if mv.contains('U') {
-        Uw(slice, variant)
-    } else if mv.contains('R') {
-        Rw(slice, variant)
-    } else if mv.contains('F') {
-        Fw(slice, variant)
-    } else if mv.contains('L') {
-        Lw(slice, variant)
-    } else if mv.contains('D') {
-        Dw(slice, variant)
-    } else if mv.contains('B') {
-        Bw(slice, variant)
-    } else if mv.contains('x') {
-        X(variant)
-    } else if mv.contains('y') {
-        Y(variant)
-    } else if mv.contains('z') {
-        Z(variant)
-    } else {
-        panic!()
-    }
-}
+    let (wide, rest) = match rest.strip_prefix('w') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
 
-fn get_slice(mv: &str) -> CubeSize {
-    if !mv.contains('w') {
-        1
-    } else {
-        mv[0..1].parse::<CubeSize>().unwrap_or(2)
-    }
-}
+    let variant = match rest {
+        "" => Standard,
+        "2" | "2'" => Double,
+        "'" => Inverse,
+        _ => return None,
+    };
 
-fn get_variant(mv: &str) -> MoveVariant {
-    if mv.contains('2') {
-        Double
-    } else if mv.contains('\'') {
-        Inverse
-    } else {
-        Standard
+    let slice = match (digits, wide) {
+        (0, _) => 2,
+        (_, true) => mv[..digits].parse::<CubeSize>().ok().filter(|&n| n > 0)?,
+        (_, false) => return None,
+    };
+
+    match (face, wide) {
+        ('U', false) => Some(U(variant)),
+        ('R', false) => Some(R(variant)),
+        ('F', false) => Some(F(variant)),
+        ('L', false) => Some(L(variant)),
+        ('D', false) => Some(D(variant)),
+        ('B', false) => Some(B(variant)),
+        ('U', true) => Some(Uw(slice, variant)),
+        ('R', true) => Some(Rw(slice, variant)),
+        ('F', true) => Some(Fw(slice, variant)),
+        ('L', true) => Some(Lw(slice, variant)),
+        ('D', true) => Some(Dw(slice, variant)),
+        ('B', true) => Some(Bw(slice, variant)),
+        ('x', false) => Some(X(variant)),
+        ('y', false) => Some(Y(variant)),
+        ('z', false) => Some(Z(variant)),
+        _ => None,
     }
 }
 
@@ -121,4 +119,21 @@ pub fn simplify_moves(moves: &[Move]) -> Vec<Move> {
     // don't recurse if moves couldn't be simplified further
     if result.len() == moves.len() { return result }
     simplify_moves(result.as_slice())
-}
\ No newline at end of file
+}
+
+/// Inverts a sequence of moves, such that applying the inverted sequence
+/// after the original sequence leaves a cube unchanged.
+///
+/// # Examples
+///
+/// Inverting the Sune:
+///
+/// ```rust
+/// use cubesim::{parse_scramble, invert_moves};
+///
+/// let sune = parse_scramble(String::from("R U R' U R U2 R'"));
+/// assert_eq!(invert_moves(&sune), parse_scramble(String::from("R U2 R' U' R U' R'")));
+/// ```
+pub fn invert_moves(moves: &[Move]) -> Vec<Move> {
+    moves.iter().rev().map(Move::inverse).collect()
+}
@@ -0,0 +1,207 @@
+use rand::{Rng, SeedableRng};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+
+use crate::generic_cube::{Move, Face, CubeSize, all_moves};
+use crate::facelet_cube::FaceletCube;
+use crate::cubie_cube::{CubieCube, permutation_parity};
+use crate::scramble_parser::{invert_moves, simplify_moves};
+use crate::thistlethwaite::solve;
+use crate::pocket_cube::{Metric, random_cube, solve_2x2};
+
+/// Generates a uniformly random solvable 3x3x3 cube.
+///
+/// Every reachable state is equally likely. The same seed always produces the same cube.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::random_state;
+///
+/// assert!(random_state(42) == random_state(42));
+/// ```
+pub fn random_state(seed: u64) -> FaceletCube {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut cube = CubieCube::solved();
+
+    cube.cp.shuffle(&mut rng);
+    cube.ep.shuffle(&mut rng);
+
+    if permutation_parity(&cube.cp) != permutation_parity(&cube.ep) {
+        cube.ep.swap(0, 1);
+    }
+
+    for i in 0..7 {
+        cube.co[i] = rng.gen_range(0..3);
+    }
+    cube.co[7] = (3 - cube.co[..7].iter().sum::<u8>() % 3) % 3;
+
+    for i in 0..11 {
+        cube.eo[i] = rng.gen_range(0..2);
+    }
+    cube.eo[11] = cube.eo[..11].iter().sum::<u8>() % 2;
+
+    cube.to_facelet_cube()
+}
+
+/// Generates a random-state scramble for a 3x3x3 cube, as required by the WCA.
+///
+/// A uniformly random state is generated and solved, and the inverse of the
+/// solution is returned. The same seed always produces the same scramble.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, random_state, random_state_scramble};
+///
+/// let scramble = random_state_scramble(42);
+/// assert_eq!(FaceletCube::new(3).apply_moves(&scramble).state(), random_state(42).state());
+/// ```
+pub fn random_state_scramble(seed: u64) -> Vec<Move> {
+    let cube = random_state(seed);
+    let solution = solve(&cube).expect("every solvable state has a solution");
+
+    simplify_moves(&invert_moves(&solution))
+}
+
+/// Generates a uniformly random 2x2x2 cube which takes at least 4 moves to solve.
+///
+/// Every such state is equally likely, with the DBL corner in place. As required
+/// by the WCA, states which can be solved in fewer than 4 moves are rejected. The
+/// same seed always produces the same cube.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{Metric, distance_2x2, random_state_2x2};
+///
+/// assert!(random_state_2x2(42) == random_state_2x2(42));
+/// assert!(distance_2x2(&random_state_2x2(42), Metric::HalfTurn).unwrap() >= 4);
+/// ```
+pub fn random_state_2x2(seed: u64) -> FaceletCube {
+    random_cube(&mut ChaCha8Rng::seed_from_u64(seed), 4)
+}
+
+/// Generates a random-state scramble for a 2x2x2 cube, as required by the WCA.
+///
+/// A random state is generated with ``random_state_2x2`` and solved optimally, and
+/// the inverse of the solution is returned. The same seed always produces the same
+/// scramble.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{FaceletCube, random_state_2x2, random_state_scramble_2x2};
+///
+/// let scramble = random_state_scramble_2x2(42);
+/// assert!(scramble.len() >= 4);
+/// assert_eq!(FaceletCube::new(2).apply_moves(&scramble).state(), random_state_2x2(42).state());
+/// ```
+pub fn random_state_scramble_2x2(seed: u64) -> Vec<Move> {
+    let cube = random_state_2x2(seed);
+    let solution = solve_2x2(&cube, Metric::HalfTurn).expect("every solvable state has a solution");
+
+    invert_moves(&solution)
+}
+
+/// Scramble lengths used by the WCA for the random-move NxNxN events.
+pub const WCA_SCRAMBLE_LENGTHS: [(CubeSize, usize); 4] = [(4, 40), (5, 60), (6, 80), (7, 100)];
+
+/// Generates a random-move scramble for a cube of a given size.
+///
+/// Moves are drawn from ``all_moves(size)``, excluding single layer wide moves which
+/// duplicate the face turns. As in WCA scramblers, wide moves only turn the U, R and F
+/// sides, since pairs like ``Uw Dw'`` on a 4x4x4 only rotate the whole cube. No two
+/// consecutive moves turn the same face, and each face is turned at most once within a
+/// sequence of moves on the same axis, so that scrambles like ``R L R'`` are never
+/// generated. The same seed always produces the same scramble.
+///
+/// Cubes smaller than 1x1x1 have nothing to turn, so their scrambles are empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::random_move_scramble;
+///
+/// let scramble = random_move_scramble(5, 60, 42);
+/// assert_eq!(scramble.len(), 60);
+/// assert_eq!(scramble, random_move_scramble(5, 60, 42));
+/// ```
+pub fn random_move_scramble(size: CubeSize, length: usize, seed: u64) -> Vec<Move> {
+    if size < 1 {
+        return vec![];
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let candidate_moves = all_moves(size)
+        .into_iter()
+        .filter(|mv| match mv {
+            Move::Uw(n, _) | Move::Fw(n, _) | Move::Rw(n, _) => *n > 1,
+            Move::Lw(_, _) | Move::Bw(_, _) | Move::Dw(_, _) => false,
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+
+    let mut scramble: Vec<Move> = Vec::with_capacity(length);
+
+    while scramble.len() < length {
+        let mv = *candidate_moves.choose(&mut rng).unwrap();
+        let face = turned_face(mv);
+
+        let is_redundant = scramble
+            .iter()
+            .rev()
+            .map(|m| turned_face(*m))
+            .take_while(|f| axis(*f) == axis(face))
+            .any(|f| f == face);
+
+        if !is_redundant {
+            scramble.push(mv);
+        }
+    }
+
+    scramble
+}
+
+/// Generates a random-move scramble with the official WCA length for a cube of a given size.
+///
+/// Returns ``None`` for sizes which are not scrambled with random moves by the WCA.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::wca_random_move_scramble;
+///
+/// assert_eq!(wca_random_move_scramble(4, 42).unwrap().len(), 40);
+/// assert_eq!(wca_random_move_scramble(7, 42).unwrap().len(), 100);
+/// assert!(wca_random_move_scramble(3, 42).is_none());
+/// ```
+pub fn wca_random_move_scramble(size: CubeSize, seed: u64) -> Option<Vec<Move>> {
+    WCA_SCRAMBLE_LENGTHS
+        .iter()
+        .find(|(s, _)| *s == size)
+        .map(|(_, length)| random_move_scramble(size, *length, seed))
+}
+
+fn turned_face(mv: Move) -> Face {
+    match mv {
+        Move::U(_) | Move::Uw(_, _) => Face::U,
+        Move::L(_) | Move::Lw(_, _) => Face::L,
+        Move::F(_) | Move::Fw(_, _) => Face::F,
+        Move::R(_) | Move::Rw(_, _) => Face::R,
+        Move::B(_) | Move::Bw(_, _) => Face::B,
+        Move::D(_) | Move::Dw(_, _) => Face::D,
+        Move::X(_) | Move::Y(_) | Move::Z(_) => Face::X,
+    }
+}
+
+fn axis(face: Face) -> Face {
+    match face {
+        Face::D => Face::U,
+        Face::L => Face::R,
+        Face::B => Face::F,
+        f => f,
+    }
+}
@@ -0,0 +1,187 @@
+//! Serde support for moves, cubes and pruning tables, enabled by the ``serde`` feature.
+//!
+//! Faces and move variants are serialized by name, and moves are serialized in WCA
+//! notation such as ``"Rw2"``. Cube states are written with one letter per facelet,
+//! in the same order as ``Cube::state``, so that they stay readable.
+
+use cgmath::Vector3;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::generic_cube::{Cube, Face, Move, CubeSize};
+use crate::facelet_cube::FaceletCube;
+use crate::geometric_cube::{GeoCube, Sticker};
+use crate::generic_solver::PruningTable;
+use crate::scramble_parser::parse_move;
+
+impl Serialize for Move {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Move {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let notation = String::deserialize(deserializer)?;
+        parse_move(&notation).ok_or_else(|| de::Error::custom(format!("invalid move notation \"{}\"", notation)))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FaceletCubeData {
+    size: CubeSize,
+    state: String,
+    ids: Vec<u16>,
+}
+
+impl Serialize for FaceletCube {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let faces = self.tracked_faces();
+
+        FaceletCubeData {
+            size: self.size(),
+            state: faces.iter().map(|(f, _)| face_letter(*f)).collect(),
+            ids: faces.iter().map(|(_, i)| *i).collect(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FaceletCube {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = FaceletCubeData::deserialize(deserializer)?;
+        let facelets = facelet_count(data.size).ok_or_else(|| de::Error::custom("cube size must be positive"))?;
+        let state = parse_state(&data.state).ok_or_else(|| de::Error::custom("invalid face letter in state"))?;
+
+        if state.len() != facelets || data.ids.len() != facelets {
+            return Err(de::Error::custom(format!("expected {} facelets for a cube of size {}", facelets, data.size)));
+        }
+
+        if data.ids.iter().any(|&i| i as usize >= facelets) {
+            return Err(de::Error::custom("facelet id out of range"));
+        }
+
+        Ok(FaceletCube::from_tracked_faces(data.size, state.into_iter().zip(data.ids).collect()))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct GeoCubeData {
+    size: CubeSize,
+    stickers: Vec<StickerData>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StickerData {
+    current: [CubeSize; 3],
+    initial: [CubeSize; 3],
+    face: Face,
+    id: CubeSize,
+}
+
+impl Serialize for GeoCube {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GeoCubeData {
+            size: self.size,
+            stickers: self.stickers
+                .iter()
+                .map(|(s, id)| StickerData { current: s.current.into(), initial: s.initial.into(), face: s.face, id: *id })
+                .collect(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoCube {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = GeoCubeData::deserialize(deserializer)?;
+        let size = data.size;
+        let facelets = facelet_count(size).ok_or_else(|| de::Error::custom("cube size must be positive"))?;
+
+        if data.stickers.len() != facelets {
+            return Err(de::Error::custom(format!("expected {} stickers for a cube of size {}", facelets, size)));
+        }
+
+        if data.stickers.iter().any(|s| !on_surface(size, s.current) || !on_surface(size, s.initial)) {
+            return Err(de::Error::custom("sticker position is not on the surface of the cube"));
+        }
+
+        let stickers = data.stickers
+            .into_iter()
+            .map(|s| (Sticker { size, current: Vector3::from(s.current), initial: Vector3::from(s.initial), face: s.face }, s.id))
+            .collect();
+
+        Ok(GeoCube { size, stickers })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PruningTableData {
+    depth: i32,
+    entries: Vec<(String, i32)>,
+}
+
+impl Serialize for PruningTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries = self.pruning_table
+            .iter()
+            .map(|(state, distance)| (state.iter().map(|&f| face_letter(f)).collect::<String>(), *distance))
+            .collect::<Vec<_>>();
+
+        // Hash map order is arbitrary, so the entries are sorted to make the output reproducible.
+        entries.sort();
+
+        PruningTableData { depth: self.depth, entries }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PruningTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = PruningTableData::deserialize(deserializer)?;
+
+        let pruning_table = data.entries
+            .into_iter()
+            .map(|(state, distance)| parse_state(&state).map(|state| (state, distance)))
+            .collect::<Option<_>>()
+            .ok_or_else(|| de::Error::custom("invalid face letter in state"))?;
+
+        Ok(PruningTable { pruning_table, depth: data.depth })
+    }
+}
+
+fn facelet_count(size: CubeSize) -> Option<usize> {
+    if size > 0 { Some(6 * (size * size) as usize) } else { None }
+}
+
+fn face_letter(face: Face) -> char {
+    match face {
+        Face::U => 'U',
+        Face::R => 'R',
+        Face::F => 'F',
+        Face::D => 'D',
+        Face::L => 'L',
+        Face::B => 'B',
+        Face::X => 'X',
+    }
+}
+
+fn parse_state(state: &str) -> Option<Vec<Face>> {
+    state
+        .chars()
+        .map(|c| match c {
+            'U' => Some(Face::U),
+            'R' => Some(Face::R),
+            'F' => Some(Face::F),
+            'D' => Some(Face::D),
+            'L' => Some(Face::L),
+            'B' => Some(Face::B),
+            'X' => Some(Face::X),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether a position is the center of a facelet of a cube of the given size.
+fn on_surface(size: CubeSize, position: [CubeSize; 3]) -> bool {
+    let on_face = position.iter().filter(|c| c.abs() == size).count() == 1;
+    let on_grid = position.iter().all(|c| c.abs() == size || (c.abs() < size && (c + size - 1) % 2 == 0));
+
+    on_face && on_grid
+}
@@ -0,0 +1,205 @@
+use cgmath::Vector3;
+
+use crate::generic_cube::{Cube, Face, MoveVariant, CubeSize};
+use crate::geometric_cube::Sticker;
+use crate::symmetry::face_move;
+
+/// Whether the cross on a face of a 3x3x3 is solved.
+///
+/// The cross is made up of the four edges on the face, which must match the
+/// centers. Like every stage check, this works in any orientation of the cube:
+/// the faces refer to the colours of the centers rather than their positions.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face, Move, MoveVariant};
+/// use cubesim::{FaceletCube, is_cross_solved, parse_scramble};
+///
+/// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R' U' x2")));
+/// assert!(is_cross_solved(&cube, Face::D));
+/// assert!(!is_cross_solved(&cube, Face::U));
+/// ```
+pub fn is_cross_solved(cube: &impl Cube, face: Face) -> bool {
+    pieces_solved(cube, |p| in_layer(p, face) && is_edge(p))
+}
+
+/// The number of solved F2L pairs around the cross on a face of a 3x3x3.
+///
+/// Each pair is a corner on the cross face together with the edge above it in the
+/// middle layer. Pairs are counted whether or not the cross itself is solved.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face};
+/// use cubesim::{FaceletCube, f2l_pairs_solved, parse_scramble};
+///
+/// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R'")));
+/// assert_eq!(f2l_pairs_solved(&cube, Face::D), 3);
+/// ```
+pub fn f2l_pairs_solved(cube: &impl Cube, face: Face) -> usize {
+    let (axis, _) = face_axis(face);
+
+    [(-2, -2), (-2, 2), (2, -2), (2, 2)]
+        .iter()
+        .filter(|&&(a, b)| pieces_solved(cube, |p| {
+            let (u, v) = (p[(axis + 1) % 3], p[(axis + 2) % 3]);
+            (u, v) == (a, b) && (in_layer(p, face) || p[axis] == 0)
+        }))
+        .count()
+}
+
+/// Whether the first two layers of a 3x3x3 are solved, with the cross on a face.
+pub fn is_f2l_solved(cube: &impl Cube, face: Face) -> bool {
+    pieces_solved(cube, |p| !in_layer(p, opposite(face)))
+}
+
+/// Whether the last layer on a face of a 3x3x3 is oriented, with the first two layers solved.
+///
+/// # Examples
+///
+/// A Sune leaves the last layer unoriented, and a second Sune does not fix it:
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face};
+/// use cubesim::{FaceletCube, is_oll_solved, parse_scramble};
+///
+/// let sune = parse_scramble(String::from("R U R' U R U2 R'"));
+/// let cube = FaceletCube::new(3).apply_moves(&sune);
+/// assert!(!is_oll_solved(&cube, Face::U));
+/// assert!(!is_oll_solved(&cube.apply_moves(&sune), Face::U));
+/// ```
+pub fn is_oll_solved(cube: &impl Cube, face: Face) -> bool {
+    stickers_solved(cube, &|p, f| if !in_layer(p, face) || f == face { f } else { Face::X })
+}
+
+/// Whether a 3x3x3 is solved up to a turn of the last layer on a face.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face, Move, MoveVariant};
+/// use cubesim::{FaceletCube, is_pll_solved};
+///
+/// let cube = FaceletCube::new(3).apply_move(Move::U(MoveVariant::Inverse));
+/// assert!(is_pll_solved(&cube, Face::U));
+/// assert!(!is_pll_solved(&cube, Face::D));
+/// ```
+pub fn is_pll_solved(cube: &impl Cube, face: Face) -> bool {
+    assert_eq!(cube.size(), 3);
+    let cube = cube.normalize_orientation();
+
+    [None, Some(MoveVariant::Standard), Some(MoveVariant::Double), Some(MoveVariant::Inverse)]
+        .iter()
+        .any(|turn| match turn {
+            Some(variant) => cube.apply_move(face_move(face, None, *variant)).is_solved(),
+            None => cube.is_solved(),
+        })
+}
+
+/// Whether every edge of a 3x3x3 is oriented with respect to the axis through a face.
+///
+/// Oriented edges can be solved without quarter turns of the face or its opposite,
+/// so ``is_eo_solved(cube, Face::F)`` is the edge orientation used by ZZ.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face, Move, MoveVariant};
+/// use cubesim::{FaceletCube, is_eo_solved};
+///
+/// let cube = FaceletCube::new(3).apply_move(Move::F(MoveVariant::Standard));
+/// assert!(!is_eo_solved(&cube, Face::F));
+/// assert!(is_eo_solved(&cube, Face::R));
+/// assert!(is_eo_solved(&cube, Face::U));
+/// ```
+pub fn is_eo_solved(cube: &impl Cube, axis: Face) -> bool {
+    let (axis, _) = face_axis(axis);
+
+    // Each edge is marked by its sticker with the most important colour, where the axis
+    // below the edge comes first, then the given axis and then the remaining axis.
+    let top = if axis == 1 { 2 } else { 1 };
+    let marked = |p: Vector3<CubeSize>, f: Face| {
+        let f_axis = face_axis(f).0;
+        is_edge(p) && (f_axis == top || (f_axis == axis && p[top] == 0))
+    };
+
+    stickers_solved(cube, &|p, f| if marked(p, f) { Face::U } else { Face::X })
+}
+
+/// Whether the Roux first block of a 3x3x3 is solved.
+///
+/// The block is the 1x2x3 block on the ``side`` face which touches the ``bottom`` face.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face, Move, MoveVariant};
+/// use cubesim::{FaceletCube, is_first_block_solved};
+///
+/// let cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+/// assert!(is_first_block_solved(&cube, Face::L, Face::D));
+/// assert!(!is_first_block_solved(&cube, Face::R, Face::D));
+/// ```
+pub fn is_first_block_solved(cube: &impl Cube, side: Face, bottom: Face) -> bool {
+    assert!(face_axis(side).0 != face_axis(bottom).0, "the side and bottom faces must be adjacent");
+
+    pieces_solved(cube, |p| in_layer(p, side) && !in_layer(p, opposite(bottom)))
+}
+
+/// Whether all the pieces selected by their solved position are in place.
+fn pieces_solved(cube: &impl Cube, pieces: impl Fn(Vector3<CubeSize>) -> bool) -> bool {
+    stickers_solved(cube, &|p, f| if pieces(p) { f } else { Face::X })
+}
+
+/// Whether a cube matches the solved cube after both are masked.
+///
+/// The mask is given the solved position of each piece, with coordinates from -2 to 2,
+/// along with the colour of the sticker. The cube is first rotated so that its centers
+/// are in place.
+fn stickers_solved<C: Cube>(cube: &C, mask: &dyn Fn(Vector3<CubeSize>, Face) -> Face) -> bool {
+    assert_eq!(cube.size(), 3, "stage checks are only defined for the 3x3x3");
+
+    let mask = |i, f| mask(piece_position(i), f);
+
+    cube.normalize_orientation().mask(&mask).state() == C::new(3).mask(&mask).state()
+}
+
+/// The position of the piece a sticker belongs to.
+fn piece_position(index: CubeSize) -> Vector3<CubeSize> {
+    Sticker::index_position(3, index).map(|c| c.clamp(-2, 2))
+}
+
+fn is_edge(position: Vector3<CubeSize>) -> bool {
+    [position.x, position.y, position.z].iter().filter(|&&c| c == 0).count() == 1
+}
+
+fn in_layer(position: Vector3<CubeSize>, face: Face) -> bool {
+    let (axis, sign) = face_axis(face);
+    position[axis] == 2 * sign
+}
+
+fn face_axis(face: Face) -> (usize, CubeSize) {
+    match face {
+        Face::R => (0, 1),
+        Face::L => (0, -1),
+        Face::U => (1, 1),
+        Face::D => (1, -1),
+        Face::F => (2, 1),
+        Face::B => (2, -1),
+        Face::X => panic!("Face::X is not a face of the cube"),
+    }
+}
+
+fn opposite(face: Face) -> Face {
+    match face {
+        Face::U => Face::D,
+        Face::D => Face::U,
+        Face::L => Face::R,
+        Face::R => Face::L,
+        Face::F => Face::B,
+        Face::B => Face::F,
+        Face::X => Face::X,
+    }
+}
@@ -0,0 +1,260 @@
+use crate::generic_cube::{Cube, Face, Move, MoveVariant, CubeSize, ORDERED_FACES, all_rotations};
+use crate::facelet_cube::FaceletCube;
+use crate::scramble_parser::invert_moves;
+
+/// A symmetry of a Rubik's Cube.
+///
+/// Each symmetry is an optional reflection through the plane between the L and R faces,
+/// followed by a whole cube rotation. The 24 rotations and their reflections make
+/// up the 48 symmetries of the cube.
+///
+/// Symmetries act on cubes by conjugation: the cube is transformed by the symmetry
+/// and its stickers are recoloured so that the centers keep their original colours.
+/// A solved cube is therefore left unchanged by every symmetry.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Symmetry {
+    rotation: Vec<Move>,
+    reflection: bool,
+    /// The face each face is taken to, following ``ORDERED_FACES``.
+    faces: [Face; 6],
+}
+
+impl Symmetry {
+    /// Creates the symmetry which optionally reflects the cube through the plane
+    /// between the L and R faces, then applies a whole cube rotation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Face, Move, MoveVariant};
+    /// use cubesim::Symmetry;
+    ///
+    /// let y = Symmetry::new(vec![Move::Y(MoveVariant::Standard)], false);
+    /// assert_eq!(y.face_map(Face::F), Face::L);
+    /// ```
+    pub fn new(rotation: Vec<Move>, reflection: bool) -> Self {
+        let rotated = FaceletCube::new(3).apply_moves(&rotation);
+
+        let faces = ORDERED_FACES.map(|face| {
+            let face = if reflection { reflect_face(face) } else { face };
+            let center = (face_position(face) * 9 + 4) as u16;
+            let position = rotated.tracked_faces().iter().position(|(_, i)| *i == center).unwrap();
+
+            ORDERED_FACES[position / 9]
+        });
+
+        Self { rotation, reflection, faces }
+    }
+
+    /// The symmetry which leaves every cube unchanged.
+    pub fn identity() -> Self {
+        Self::new(vec![], false)
+    }
+
+    /// The whole cube rotation applied after the reflection.
+    pub fn rotation(&self) -> &[Move] {
+        &self.rotation
+    }
+
+    /// Whether the cube is reflected through the plane between the L and R faces.
+    pub fn reflection(&self) -> bool {
+        self.reflection
+    }
+
+    /// The 24 rotational symmetries of the cube.
+    pub fn rotations() -> Vec<Self> {
+        all_rotations()
+            .into_iter()
+            .map(|rotation| Self::new(rotation, false))
+            .collect()
+    }
+
+    /// All 48 symmetries of the cube, including reflections.
+    pub fn all() -> Vec<Self> {
+        [false, true]
+            .iter()
+            .flat_map(|&reflection| all_rotations()
+                .into_iter()
+                .map(move |rotation| Self::new(rotation, reflection)))
+            .collect()
+    }
+
+    /// The symmetry which undoes this symmetry.
+    pub fn inverse(&self) -> Self {
+        let rotation = invert_moves(&self.rotation);
+
+        if self.reflection {
+            let reflection = Self::new(vec![], true);
+            Self::new(reflection.conjugate_moves(&rotation), true)
+        } else {
+            Self::new(rotation, false)
+        }
+    }
+
+    /// The face that each face is taken to by this symmetry.
+    pub fn face_map(&self, face: Face) -> Face {
+        match face {
+            Face::X => Face::X,
+            face => self.faces[face_position(face)],
+        }
+    }
+
+    /// Conjugates a sequence of moves by this symmetry.
+    ///
+    /// Applying the conjugated moves to a solved cube gives the same cube as
+    /// conjugating the result of applying the original moves.
+    ///
+    /// # Examples
+    ///
+    /// Mirroring the Sune:
+    ///
+    /// ```rust
+    /// use cubesim::{parse_scramble, Symmetry};
+    ///
+    /// let mirror = Symmetry::new(vec![], true);
+    /// let sune = parse_scramble(String::from("R U R' U R U2 R'"));
+    /// assert_eq!(mirror.conjugate_moves(&sune), parse_scramble(String::from("L' U' L U' L' U2 L")));
+    /// ```
+    pub fn conjugate_moves(&self, moves: &[Move]) -> Vec<Move> {
+        let map = |f: Face| self.face_map(f);
+
+        moves.iter().map(|mv| {
+            let variant = if self.reflection { mv.inverse().get_variant() } else { mv.get_variant() };
+
+            match *mv {
+                Move::U(_) => face_move(map(Face::U), None, variant),
+                Move::L(_) => face_move(map(Face::L), None, variant),
+                Move::F(_) => face_move(map(Face::F), None, variant),
+                Move::R(_) => face_move(map(Face::R), None, variant),
+                Move::B(_) => face_move(map(Face::B), None, variant),
+                Move::D(_) => face_move(map(Face::D), None, variant),
+                Move::Uw(n, _) => face_move(map(Face::U), Some(n), variant),
+                Move::Lw(n, _) => face_move(map(Face::L), Some(n), variant),
+                Move::Fw(n, _) => face_move(map(Face::F), Some(n), variant),
+                Move::Rw(n, _) => face_move(map(Face::R), Some(n), variant),
+                Move::Bw(n, _) => face_move(map(Face::B), Some(n), variant),
+                Move::Dw(n, _) => face_move(map(Face::D), Some(n), variant),
+                Move::X(_) => cube_rotation(map(Face::R), variant),
+                Move::Y(_) => cube_rotation(map(Face::U), variant),
+                Move::Z(_) => cube_rotation(map(Face::F), variant),
+            }
+        }).collect()
+    }
+}
+
+impl FaceletCube {
+    /// Conjugates the cube by a symmetry.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant};
+    /// use cubesim::{FaceletCube, Symmetry};
+    ///
+    /// let y = Symmetry::new(vec![Move::Y(MoveVariant::Standard)], false);
+    /// let cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+    /// assert!(cube.conjugate(&y) == FaceletCube::new(3).apply_move(Move::F(MoveVariant::Standard)));
+    /// ```
+    pub fn conjugate(&self, symmetry: &Symmetry) -> Self {
+        let size = self.size();
+        let mut rotation_map = vec![0; (6 * size * size) as usize];
+
+        for (position, (_, i)) in FaceletCube::new(size).apply_moves(&symmetry.rotation).tracked_faces().iter().enumerate() {
+            rotation_map[*i as usize] = position;
+        }
+
+        let position_map = |i: usize| if symmetry.reflection {
+            rotation_map[reflect_index(size, i)]
+        } else {
+            rotation_map[i]
+        };
+
+        let mut faces = vec![(Face::X, 0); self.tracked_faces().len()];
+
+        for (position, (face, i)) in self.tracked_faces().iter().enumerate() {
+            let face = if *face == Face::X { Face::X } else { symmetry.face_map(*face) };
+            faces[position_map(position)] = (face, position_map(*i as usize) as u16);
+        }
+
+        FaceletCube::from_tracked_faces(size, faces)
+    }
+
+    /// The canonical representative of the cube under a set of symmetries,
+    /// together with the symmetry which conjugates the cube into it.
+    ///
+    /// The canonical representative is the conjugate with the lexicographically
+    /// smallest state, so two cubes that are equivalent under the given symmetries
+    /// share the same canonical representative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant};
+    /// use cubesim::{FaceletCube, Symmetry};
+    ///
+    /// let r_cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+    /// let l_cube = FaceletCube::new(3).apply_move(Move::L(MoveVariant::Inverse));
+    ///
+    /// let (canonical, symmetry) = r_cube.canonical_form(&Symmetry::all());
+    /// assert!(canonical == l_cube.canonical_form(&Symmetry::all()).0);
+    /// assert!(r_cube.conjugate(&symmetry) == canonical);
+    /// ```
+    pub fn canonical_form(&self, symmetries: &[Symmetry]) -> (Self, Symmetry) {
+        symmetries
+            .iter()
+            .map(|symmetry| (self.conjugate(symmetry), symmetry.clone()))
+            .min_by(|(a, _), (b, _)| a.state().cmp(&b.state()))
+            .unwrap_or_else(|| (self.clone(), Symmetry::identity()))
+    }
+}
+
+fn face_position(face: Face) -> usize {
+    ORDERED_FACES.iter().position(|&f| f == face).unwrap()
+}
+
+fn reflect_face(face: Face) -> Face {
+    match face {
+        Face::L => Face::R,
+        Face::R => Face::L,
+        f => f,
+    }
+}
+
+fn reflect_index(size: CubeSize, index: usize) -> usize {
+    let size = size as usize;
+    let face = reflect_face(ORDERED_FACES[index / (size * size)]);
+    let row = (index % (size * size)) / size;
+    let col = index % size;
+
+    face_position(face) * size * size + row * size + (size - 1 - col)
+}
+
+pub(crate) fn face_move(face: Face, layers: Option<CubeSize>, variant: MoveVariant) -> Move {
+    match (face, layers) {
+        (Face::U, None) => Move::U(variant),
+        (Face::L, None) => Move::L(variant),
+        (Face::F, None) => Move::F(variant),
+        (Face::R, None) => Move::R(variant),
+        (Face::B, None) => Move::B(variant),
+        (Face::D, None) => Move::D(variant),
+        (Face::U, Some(n)) => Move::Uw(n, variant),
+        (Face::L, Some(n)) => Move::Lw(n, variant),
+        (Face::F, Some(n)) => Move::Fw(n, variant),
+        (Face::R, Some(n)) => Move::Rw(n, variant),
+        (Face::B, Some(n)) => Move::Bw(n, variant),
+        (Face::D, Some(n)) => Move::Dw(n, variant),
+        (Face::X, _) => unreachable!(),
+    }
+}
+
+fn cube_rotation(face: Face, variant: MoveVariant) -> Move {
+    match face {
+        Face::R => Move::X(variant),
+        Face::L => Move::X(variant).inverse(),
+        Face::U => Move::Y(variant),
+        Face::D => Move::Y(variant).inverse(),
+        Face::F => Move::Z(variant),
+        Face::B => Move::Z(variant).inverse(),
+        Face::X => unreachable!(),
+    }
+}
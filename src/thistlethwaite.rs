@@ -1,9 +1,10 @@
 use lazy_static::lazy_static;
 
-use crate::generic_cube::{Cube, Face, Move, MoveVariant, CubeSize, all_moves};
+use crate::generic_cube::{Cube, Face, Move, MoveVariant, CubeSize, ORDERED_FACES, all_moves};
 use crate::generic_cube::{sticker_index as S};
 use crate::facelet_cube::FaceletCube;
 use crate::generic_solver::{Solver, PruningTable, ida_star};
+use crate::pieces::{PieceType, stickers_of};
 
 /// Solves a 3x3x3 Cube using the Thistlethwaite Algorithm.
 /// 
@@ -11,6 +12,12 @@ use crate::generic_solver::{Solver, PruningTable, ida_star};
 /// The solutions generated are NOT optimal and will take on average 60 moves.
 /// While this function will work on a ``GeoCube``, it is highly recommended to
 /// use a ``FaceletCube`` for optimal performance.
+///
+/// Cubes which are not in standard orientation are first rotated into it, so the
+/// solution may begin with whole cube rotations (``x``, ``y`` and ``z``). These are
+/// not turns, so callers counting moves or sending them to a robot should skip
+/// them or handle them separately. Cubes already in standard orientation, such as
+/// any cube scrambled without rotations, get no rotations.
 /// 
 /// [Jaap's Puzzle Page]: https://www.jaapsch.net/puzzles/thistle.htm
 /// 
@@ -18,7 +25,7 @@ use crate::generic_solver::{Solver, PruningTable, ida_star};
 /// 
 /// Solve the superflip position:
 /// 
-/// ```rust
+/// ```rust,no_run
 /// use cubesim::prelude::{Cube};
 /// use cubesim::FaceletCube;
 /// use cubesim::parse_scramble;
@@ -27,11 +34,11 @@ use crate::generic_solver::{Solver, PruningTable, ida_star};
 /// let cube = FaceletCube::new(3).apply_moves(
 ///     &parse_scramble(String::from("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2"))
 ///  );
-//// assert!(cube.apply_moves(&solve(&cube).unwrap()).is_solved());
+/// assert!(cube.apply_moves(&solve(&cube).unwrap()).is_solved());
 /// ```
 pub fn solve(cube: &impl Cube) -> Option<Vec<Move>> {
-    let mut solution = vec![];
-    let mut cube = cube.clone();
+    let mut solution = cube.orientation().unwrap_or_default();
+    let mut cube = cube.apply_moves(&solution);
 
     for phase in [phase1, phase2, phase3, phase4] {
         let mut phase_solution = phase(&cube)?;
@@ -66,7 +73,7 @@ pub fn phase1(cube: &impl Cube) -> Option<Vec<Move>> {
         static ref SOLVER: Solver = Solver::new(all_moves(3), (*PRUNING_TABLE).clone());
     }
 
-    ida_star(&cube.mask(&*MASK), &*SOLVER, 10)
+    ida_star(&cube.mask(&*MASK), &SOLVER, 10)
 }
 
 pub fn phase2(cube: &impl Cube) -> Option<Vec<Move>> {
@@ -106,13 +113,13 @@ pub fn phase2(cube: &impl Cube) -> Option<Vec<Move>> {
 
         static ref PRUNING_TABLE: PruningTable = {
             let pruning_depth = 5;
-            PruningTable::new(&[FaceletCube::new(3).mask(&*MASK)], pruning_depth, &*MOVES)
+            PruningTable::new(&[FaceletCube::new(3).mask(&*MASK)], pruning_depth, &MOVES)
         };
 
         static ref SOLVER: Solver = Solver::new((*MOVES).clone(), (*PRUNING_TABLE).clone());
     }
 
-    ida_star(&cube.mask(&*MASK), &*SOLVER, 10)
+    ida_star(&cube.mask(&*MASK), &SOLVER, 10)
 }
 
 pub fn phase3(cube: &impl Cube) -> Option<Vec<Move>>  {
@@ -121,15 +128,12 @@ pub fn phase3(cube: &impl Cube) -> Option<Vec<Move>>  {
 
     lazy_static! {
         static ref MASK: Box<dyn Fn(CubeSize, Face) -> Face + Sync> = {
-            let cp_pieces = [U, D, F, B, L, R].iter()
-                .map(|f| [1, 3, 7, 9].map(|x| S(3, *f, x)))
-                .collect::<Vec<_>>()
-                .concat();
+            let cp_pieces = stickers_of(3, PieceType::Corner);
 
-            let ep_pieces = [F, B, L, R].iter()
-                .map(|f| [2, 4, 6, 8].map(|x| S(3, *f, x)))
-                .collect::<Vec<_>>()
-                .concat();
+            let ep_pieces = stickers_of(3, PieceType::Midge)
+                .into_iter()
+                .filter(|&i| ![U, D].contains(&ORDERED_FACES[(i / 9) as usize]))
+                .collect::<Vec<_>>();
 
             let face = |f| if f == B { F }
                            else if f == L { R }
@@ -145,7 +149,7 @@ pub fn phase3(cube: &impl Cube) -> Option<Vec<Move>>  {
         static ref G2_SOLVED_STATES: PruningTable = PruningTable::new(
             &[FaceletCube::new(3).mask(&*MASK)], 
             10,
-            &vec![Move::U(Double), Move::D(Double), Move::F(Double), Move::B(Double), Move::L(Double), Move::R(Double)]
+            &[Move::U(Double), Move::D(Double), Move::F(Double), Move::B(Double), Move::L(Double), Move::R(Double)]
         );
 
         static ref MOVES: Vec<Move> = vec![
@@ -156,13 +160,13 @@ pub fn phase3(cube: &impl Cube) -> Option<Vec<Move>>  {
 
         static ref PRUNING_TABLE: PruningTable = {
             let pruning_depth = 5;
-            PruningTable::from_existing_table(&G2_SOLVED_STATES, pruning_depth, &*MOVES)
+            PruningTable::from_existing_table(&G2_SOLVED_STATES, pruning_depth, &MOVES)
         };
 
         static ref SOLVER: Solver = Solver::new((*MOVES).clone(), (*PRUNING_TABLE).clone());
     }
 
-    ida_star(&cube.mask(&*MASK), &*SOLVER, 13)
+    ida_star(&cube.mask(&*MASK), &SOLVER, 13)
 }
 
 pub fn phase4(cube: &impl Cube) -> Option<Vec<Move>> {
@@ -7,7 +7,7 @@ use test_case::test_case;
 use cubesim::prelude::*;
 use cubesim::prelude::Face::*;
 use cubesim::{GeoCube, FaceletCube};
-use cubesim::solved_state;
+use cubesim::{solved_state, parse_scramble, all_rotations};
 
 //
 // State Tests
@@ -183,4 +183,64 @@ fn generic_solved_state() {
         L, L, L, L, L, L, L, L, L,
         B, B, B, B, B, B, B, B, B
     ]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn scrambled_geometric_state_matches_facelet_state() {
+    let scramble = parse_scramble(String::from("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2 x y' z2"));
+
+    assert_eq!(
+        GeoCube::new(3).apply_moves(&scramble).state(),
+        FaceletCube::new(3).apply_moves(&scramble).state()
+    );
+}
+
+//
+// Orientation Tests
+//
+#[test_case(GeoCube::new(3) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(3) ; "Facelet Cube")]
+fn solved_cube_orientation(cube: impl Cube) {
+    assert_eq!(cube.orientation(), Some(vec![]));
+}
+
+#[test_case(GeoCube::new(3) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(3) ; "Facelet Cube")]
+fn rotated_cube_orientation(cube: impl Cube) {
+    let scrambled = cube.apply_moves(&parse_scramble(String::from("R U F' L2 D B")));
+
+    for rotation in all_rotations() {
+        let rotated = scrambled.apply_moves(&rotation);
+        let orientation = rotated.orientation().unwrap();
+
+        assert_eq!(rotated.apply_moves(&orientation).state(), scrambled.state());
+        assert_eq!(rotated.normalize_orientation().state(), scrambled.state());
+        assert!(rotated.eq_up_to_rotation(&scrambled));
+    }
+}
+
+#[test_case(GeoCube::new(3) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(3) ; "Facelet Cube")]
+fn masked_cube_orientation(cube: impl Cube) {
+    assert_eq!(cube.mask(&|_, _| X).orientation(), None);
+}
+
+#[test_case(GeoCube::new(3) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(3) ; "Facelet Cube")]
+fn mask_by_solved_index(cube: impl Cube) {
+    let scrambled = cube.apply_moves(&parse_scramble(String::from("R U F' L2 D B")));
+    let masked = scrambled.mask(&|i, f| if i == 4 || i == 22 { f } else { X });
+    let expected = FaceletCube::new(3)
+        .apply_moves(&parse_scramble(String::from("R U F' L2 D B")))
+        .mask(&|i, f| if i == 4 || i == 22 { f } else { X });
+
+    assert_eq!(masked.state(), expected.state());
+    assert_eq!(masked.state().iter().filter(|&&f| f != X).count(), 2);
+}
+
+#[test_case(GeoCube::new(3) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(3) ; "Facelet Cube")]
+fn different_states_not_equal_up_to_rotation(cube: impl Cube) {
+    let scrambled = cube.apply_moves(&parse_scramble(String::from("R U F' L2 D B")));
+    assert!(!scrambled.eq_up_to_rotation(&scrambled.apply_move(Move::U(MoveVariant::Standard))));
+}
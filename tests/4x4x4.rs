@@ -7,7 +7,7 @@ use test_case::test_case;
 use cubesim::prelude::*;
 use cubesim::prelude::Face::*;
 use cubesim::{GeoCube, FaceletCube};
-use cubesim::solved_state;
+use cubesim::{solved_state, parse_scramble, all_rotations};
 
 //
 // State Tests
@@ -191,4 +191,30 @@ fn generic_solved_state() {
         L, L, L, L, L, L, L, L, L, L, L, L, L, L, L, L,
         B, B, B, B, B, B, B, B, B, B, B, B, B, B, B, B
     ]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn scrambled_geometric_state_matches_facelet_state() {
+    let scramble = parse_scramble(String::from("Rw U2 x Fw' L2 D Bw2 y' R' Uw F2 z Lw Dw2 B'"));
+
+    assert_eq!(
+        GeoCube::new(4).apply_moves(&scramble).state(),
+        FaceletCube::new(4).apply_moves(&scramble).state()
+    );
+}
+
+//
+// Orientation Tests
+//
+#[test_case(GeoCube::new(4) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(4) ; "Facelet Cube")]
+fn rotated_cube_orientation(cube: impl Cube) {
+    let scrambled = cube.apply_moves(&parse_scramble(String::from("Rw U2 Fw' R U F' Uw2 R'")));
+
+    for rotation in all_rotations() {
+        let rotated = scrambled.apply_moves(&rotation);
+
+        assert_eq!(rotated.normalize_orientation().state(), scrambled.state());
+        assert!(rotated.eq_up_to_rotation(&scrambled));
+    }
+}
@@ -0,0 +1,109 @@
+//
+// Animation Tests
+//
+
+use cgmath::InnerSpace;
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{GeoCube, Easing, StickerTransform, Timeline, all_moves, parse_scramble};
+
+fn assert_close(a: &[StickerTransform], b: &[StickerTransform]) {
+    assert_eq!(a.len(), b.len());
+
+    for (s, t) in a.iter().zip(b) {
+        assert_eq!(s.face, t.face);
+        assert!((s.center - t.center).magnitude() < 1e-9);
+        assert!((s.normal - t.normal).magnitude() < 1e-9);
+    }
+}
+
+#[test_case(Easing::Linear)]
+#[test_case(Easing::EaseIn)]
+#[test_case(Easing::EaseOut)]
+#[test_case(Easing::EaseInOut)]
+fn easing_is_monotonic(easing: Easing) {
+    assert_eq!(easing.apply(0.0), 0.0);
+    assert_eq!(easing.apply(1.0), 1.0);
+
+    let values = (0..=100).map(|k| easing.apply(k as f64 / 100.0)).collect::<Vec<_>>();
+    assert!(values.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test_case(2)]
+#[test_case(3)]
+#[test_case(4)]
+fn animation_ends_on_turned_cube(size: i32) {
+    let cube = GeoCube::new(size).apply_moves(&parse_scramble(String::from("R U' F2")));
+
+    for mv in all_moves(size) {
+        assert_close(&cube.animate_move(mv, 0.0, Easing::default()), &cube.sticker_transforms());
+        assert_close(&cube.animate_move(mv, 1.0, Easing::default()), &cube.apply_move(mv).sticker_transforms());
+    }
+}
+
+#[test]
+fn animation_rotates_turning_layer() {
+    let cube = GeoCube::new(3);
+    let frame = cube.animate_move(Move::U(MoveVariant::Standard), 0.5, Easing::Linear);
+    let mut moved = 0;
+
+    for (before, during) in cube.sticker_transforms().iter().zip(&frame) {
+        assert!((during.center.magnitude() - before.center.magnitude()).abs() < 1e-9);
+        assert!((during.center.y - before.center.y).abs() < 1e-9);
+
+        if (during.center - before.center).magnitude() > 1e-9 {
+            assert!(before.center.y >= 1.0);
+            moved += 1;
+        }
+    }
+
+    assert_eq!(moved, 9 + 4 * 3 - 1);
+}
+
+#[test]
+fn turning_stickers_flagged() {
+    let cube = GeoCube::new(3);
+    let frame = cube.animate_move(Move::U(MoveVariant::Standard), 0.5, Easing::Linear);
+
+    assert_eq!(frame.iter().filter(|s| s.turning).count(), 9 + 4 * 3);
+    assert!(cube.sticker_transforms().iter().all(|s| !s.turning));
+}
+
+#[test]
+fn sticker_corners_face_outwards() {
+    for sticker in GeoCube::new(3).animate_move(Move::F(MoveVariant::Double), 0.3, Easing::Linear) {
+        let [a, b, c, _] = sticker.corners(0.1);
+        let normal = (b - a).cross(c - a).normalize();
+
+        assert!((normal - sticker.normal).magnitude() < 1e-9);
+        assert!(((b - a).magnitude() - 1.6).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn timeline_positions() {
+    let moves = parse_scramble(String::from("R U R' U'"));
+    let timeline = Timeline::new(&GeoCube::new(3), &moves);
+
+    assert_eq!(timeline.duration(), 4.0);
+    assert_eq!(timeline.position(-0.5), None);
+    assert_eq!(timeline.position(0.0), Some((0, 0.0)));
+    assert_eq!(timeline.position(3.75), Some((3, 0.75)));
+    assert_eq!(timeline.position(4.0), None);
+    assert_eq!(timeline.states().len(), 5);
+}
+
+#[test]
+fn timeline_frames() {
+    let cube = GeoCube::new(3);
+    let moves = parse_scramble(String::from("R U R' U'"));
+    let timeline = Timeline::new(&cube, &moves);
+    let frames = timeline.frames(4.0).collect::<Vec<_>>();
+
+    assert_eq!(frames.len(), 17);
+    assert_close(&frames[0], &cube.sticker_transforms());
+    assert_close(&frames[4], &cube.apply_moves(&moves[..1]).sticker_transforms());
+    assert_close(&frames[16], &cube.apply_moves(&moves).sticker_transforms());
+    assert_close(&timeline.frame(10.0), &frames[16]);
+}
@@ -0,0 +1,79 @@
+//
+// Color Scheme Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{GeoCube, FaceletCube, Color, ColorScheme};
+use cubesim::parse_scramble;
+
+#[test_case(GeoCube::new(3) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(3) ; "Facelet Cube")]
+fn solved_state_colors(cube: impl Cube) {
+    let colors = cube.state_colors(&ColorScheme::western());
+
+    for (i, color) in [Color::white(), Color::red(), Color::green(), Color::yellow(), Color::orange(), Color::blue()].iter().enumerate() {
+        assert!(colors[i * 9..(i + 1) * 9].iter().all(|c| c == color));
+    }
+}
+
+#[test]
+fn masked_state_colors() {
+    let colors = FaceletCube::new(2).mask(&|_, _| Face::X).state_colors(&ColorScheme::default());
+    assert!(colors.iter().all(|c| *c == Color::gray()));
+}
+
+#[test]
+fn custom_color_scheme() {
+    let pink = Color::new("pink", 'P', [255, 105, 180]);
+    let scheme = ColorScheme::western().with_color(Face::U, pink.clone());
+
+    assert_eq!(scheme.color(Face::U), &pink);
+    assert_eq!(scheme.face('P'), Some(Face::U));
+    assert_eq!(scheme.face('W'), None);
+}
+
+#[test]
+fn japanese_color_scheme() {
+    let scheme = ColorScheme::japanese();
+
+    assert_eq!(scheme.face('B'), Some(Face::D));
+    assert_eq!(scheme.face('Y'), Some(Face::B));
+}
+
+#[test]
+fn color_hex() {
+    assert_eq!(Color::orange().hex(), "#ff5800");
+}
+
+#[test]
+fn parse_state_round_trip() {
+    for size in 2..=5 {
+        let cube = FaceletCube::new(size).apply_moves(&parse_scramble(String::from("R U' F2 Rw D")));
+        let letters = cube.state_colors(&ColorScheme::western()).iter().map(|c| c.letter).collect::<String>();
+
+        assert_eq!(ColorScheme::western().parse_state(&letters).unwrap().state(), cube.state());
+    }
+}
+
+#[test]
+fn parse_state_tracks_pieces() {
+    for size in 2..=3 {
+        let cube = FaceletCube::new(size).apply_moves(&parse_scramble(String::from("R U' F2 D")));
+        let letters = cube.state_colors(&ColorScheme::western()).iter().map(|c| c.letter).collect::<String>();
+
+        assert!(ColorScheme::western().parse_state(&letters).unwrap() == cube);
+    }
+}
+
+#[test]
+fn parse_invalid_state() {
+    assert!(ColorScheme::western().parse_state("WWWW").is_none());
+    assert!(ColorScheme::western().parse_state(&"Q".repeat(54)).is_none());
+
+    // Two corners with the same colours.
+    let letters = "WWWWWWWWW RRRRRRRRR GGGGGGGGG YYYYYYYYY OOOOOOOOO BBBBBBBBB".replacen('W', "Y", 1);
+    assert!(ColorScheme::western().parse_state(&letters).is_none());
+    assert!(ColorScheme::western().parse_state(&"X".repeat(54)).is_none());
+}
@@ -0,0 +1,103 @@
+//
+// Import Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, PieceType, random_move_scramble, sticker_index, stickers_of, solve};
+
+const NON_CENTERS: [PieceType; 3] = [PieceType::Corner, PieceType::Midge, PieceType::Wing];
+
+#[test_case(2)]
+#[test_case(3)]
+#[test_case(4)]
+#[test_case(5)]
+#[test_case(6)]
+#[test_case(7)]
+fn imported_state_tracks_pieces(size: i32) {
+    for seed in 0..5 {
+        let scrambled = FaceletCube::new(size).apply_moves(&random_move_scramble(size, 60, seed));
+        let imported = FaceletCube::from_state(&scrambled.state()).unwrap();
+
+        assert_eq!(imported.state(), scrambled.state());
+
+        // Corners and edges can be told apart by their colours, so they are tracked exactly.
+        let edges_and_corners = NON_CENTERS.iter().flat_map(|&t| stickers_of(size, t)).collect::<Vec<_>>();
+        let mask = |i, f| if edges_and_corners.contains(&i) { f } else { Face::X };
+        assert_eq!(imported.mask(&mask).state(), scrambled.mask(&mask).state());
+
+        // Identical centers may be swapped, but masks by colour still agree.
+        let mask = |_, f| if f == Face::U { f } else { Face::X };
+        assert_eq!(imported.mask(&mask).state(), scrambled.mask(&mask).state());
+    }
+}
+
+#[test_case(2)]
+#[test_case(3)]
+fn small_cubes_imported_exactly(size: i32) {
+    let scrambled = FaceletCube::new(size).apply_moves(&random_move_scramble(size, 30, 7));
+    assert!(FaceletCube::from_state(&scrambled.state()).unwrap() == scrambled);
+}
+
+#[test]
+fn imported_state_solved_by_thistlethwaite() {
+    let scrambled = FaceletCube::new(3).apply_moves(&random_move_scramble(3, 30, 3));
+    let imported = FaceletCube::from_state(&scrambled.state()).unwrap();
+
+    let solution = solve(&imported).unwrap();
+    assert!(scrambled.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn wrong_number_of_stickers() {
+    assert!(FaceletCube::from_state(&[]).is_none());
+    assert!(FaceletCube::from_state(&FaceletCube::new(3).state()[1..]).is_none());
+}
+
+#[test]
+fn masked_stickers_rejected() {
+    let mut state = FaceletCube::new(3).state();
+    state[0] = Face::X;
+
+    assert!(FaceletCube::from_state(&state).is_none());
+}
+
+#[test]
+fn duplicate_pieces_rejected() {
+    // Recolouring the UF edge as a second UR edge.
+    let mut state = FaceletCube::new(3).state();
+    state[sticker_index(3, Face::F, 2) as usize] = Face::R;
+
+    assert!(FaceletCube::from_state(&state).is_none());
+}
+
+#[test]
+fn mirrored_corner_rejected() {
+    let mut state = FaceletCube::new(3).state();
+    state.swap(sticker_index(3, Face::R, 1) as usize, sticker_index(3, Face::F, 3) as usize);
+
+    assert!(FaceletCube::from_state(&state).is_none());
+}
+
+#[test]
+fn flipped_wing_rejected() {
+    let mut state = FaceletCube::new(4).state();
+    state.swap(sticker_index(4, Face::U, 14) as usize, sticker_index(4, Face::F, 2) as usize);
+
+    assert!(FaceletCube::from_state(&state).is_none());
+}
+
+#[test]
+fn twisted_corner_accepted() {
+    // A twisted corner cannot be reached by moves, but its stickers can still be tracked.
+    let mut state = FaceletCube::new(3).state();
+    let corner = [sticker_index(3, Face::U, 9), sticker_index(3, Face::R, 1), sticker_index(3, Face::F, 3)];
+    let colors = corner.map(|i| state[i as usize]);
+
+    for (n, &i) in corner.iter().enumerate() {
+        state[i as usize] = colors[(n + 1) % 3];
+    }
+
+    assert_eq!(FaceletCube::from_state(&state).unwrap().state(), state);
+}
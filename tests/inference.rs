@@ -0,0 +1,169 @@
+//
+// Inference Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, Completion, complete_state, random_state, random_move_scramble, sticker_index};
+
+fn masked(state: &[Face], unknown: &[usize]) -> Vec<Face> {
+    let mut state = state.to_vec();
+    unknown.iter().for_each(|&i| state[i] = Face::X);
+    state
+}
+
+#[test_case(0)]
+#[test_case(1)]
+#[test_case(2)]
+#[test_case(3)]
+fn solvable_states_complete_to_themselves(seed: u64) {
+    let state = random_state(seed).state();
+    assert_eq!(complete_state(&state, 10), Completion::Unique(state));
+}
+
+#[test_case(0)]
+#[test_case(1)]
+#[test_case(2)]
+fn one_sticker_per_corner_unknown(seed: u64) {
+    let state = random_state(seed).state();
+
+    // The U and D stickers of every corner, along with two centers and a whole edge.
+    let unknown = [0, 2, 6, 8, 27, 29, 33, 35, 13, 40, 5, 10];
+    assert_eq!(complete_state(&masked(&state, &unknown), 10), Completion::Unique(state));
+}
+
+#[test]
+fn last_piece_follows_from_the_others() {
+    let state = random_state(4).state();
+
+    // The UR edge is left over once the other edges are placed, and its flip is fixed.
+    let unknown = [sticker_index(3, Face::U, 6) as usize, sticker_index(3, Face::R, 2) as usize];
+    assert_eq!(complete_state(&masked(&state, &unknown), 10), Completion::Unique(state.clone()));
+
+    // Likewise for a corner, whose twist is fixed by the others.
+    let unknown = [0, 36, 47];
+    assert_eq!(complete_state(&masked(&state, &unknown), 10), Completion::Unique(state));
+}
+
+#[test]
+fn ambiguous_states_list_candidates() {
+    let state = random_state(5).state();
+    let unknown = [0, 36, 47, 2, 45, 11];
+
+    match complete_state(&masked(&state, &unknown), 10) {
+        Completion::Ambiguous(candidates) => {
+            assert_eq!(candidates.len(), 3);
+            assert!(candidates.contains(&state));
+
+            for candidate in candidates {
+                assert_eq!(complete_state(&candidate, 10), Completion::Unique(candidate.clone()));
+            }
+        }
+        completion => panic!("expected an ambiguous completion, got {:?}", completion),
+    }
+}
+
+#[test]
+fn candidates_limited() {
+    match complete_state(&[Face::X; 54], 4) {
+        Completion::Ambiguous(candidates) => assert_eq!(candidates.len(), 4),
+        completion => panic!("expected an ambiguous completion, got {:?}", completion),
+    }
+}
+
+#[test]
+fn rotated_cube_completed() {
+    let cube = FaceletCube::new(3).apply_moves(&random_move_scramble(3, 25, 9));
+    let rotated = cube.apply_moves(&[Move::X(MoveVariant::Standard), Move::Y(MoveVariant::Inverse)]).state();
+
+    // Only the U and F centers are needed to fix the orientation.
+    let unknown = [13, 31, 40, 49];
+    assert_eq!(complete_state(&masked(&rotated, &unknown), 10), Completion::Unique(rotated));
+}
+
+#[test]
+fn twisted_corner_invalid() {
+    let mut state = FaceletCube::new(3).state();
+    let corner = [sticker_index(3, Face::U, 9), sticker_index(3, Face::R, 1), sticker_index(3, Face::F, 3)];
+    let colors = corner.map(|i| state[i as usize]);
+
+    for (n, &i) in corner.iter().enumerate() {
+        state[i as usize] = colors[(n + 1) % 3];
+    }
+
+    assert_eq!(complete_state(&state, 10), Completion::Invalid);
+}
+
+#[test]
+fn flipped_edge_invalid() {
+    let mut state = FaceletCube::new(3).state();
+    state.swap(sticker_index(3, Face::U, 8) as usize, sticker_index(3, Face::F, 2) as usize);
+
+    assert_eq!(complete_state(&state, 10), Completion::Invalid);
+}
+
+#[test]
+fn swapped_edges_invalid() {
+    let mut state = FaceletCube::new(3).state();
+    state.swap(sticker_index(3, Face::F, 6) as usize, sticker_index(3, Face::B, 6) as usize);
+    state.swap(sticker_index(3, Face::R, 4) as usize, sticker_index(3, Face::L, 4) as usize);
+
+    assert_eq!(complete_state(&state, 10), Completion::Invalid);
+}
+
+#[test]
+fn impossible_piece_found_quickly() {
+    // A corner with two opposite colours cannot exist, whatever the other stickers are.
+    let mut state = vec![Face::X; 54];
+    state[sticker_index(3, Face::D, 9) as usize] = Face::U;
+    state[sticker_index(3, Face::R, 9) as usize] = Face::D;
+
+    assert_eq!(complete_state(&state, 10), Completion::Invalid);
+}
+
+#[test]
+fn conflicting_centers_invalid() {
+    let mut state = FaceletCube::new(3).state();
+    state[4] = Face::D;
+
+    assert_eq!(complete_state(&state, 10), Completion::Invalid);
+}
+
+#[test_case(0)]
+#[test_case(1)]
+fn two_by_two_corner_completed(seed: u64) {
+    let state = FaceletCube::new(2).apply_moves(&random_move_scramble(2, 20, seed)).state();
+
+    // A whole corner is missing, but it is the only piece left and its twist is fixed.
+    let unknown = [0, 16, 21];
+    assert_eq!(complete_state(&masked(&state, &unknown), 10), Completion::Unique(state));
+}
+
+#[test]
+fn twisted_corner_with_unknown_edges_found_quickly() {
+    let mut state = FaceletCube::new(3).state();
+    let corner = [sticker_index(3, Face::U, 9), sticker_index(3, Face::R, 1), sticker_index(3, Face::F, 3)];
+    let colors = corner.map(|i| state[i as usize]);
+
+    for (n, &i) in corner.iter().enumerate() {
+        state[i as usize] = colors[(n + 1) % 3];
+    }
+
+    // Every edge is unknown, so the twist must be caught before the edges are tried out.
+    for face in [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B] {
+        for k in [2, 4, 6, 8] {
+            state[sticker_index(3, face, k) as usize] = Face::X;
+        }
+    }
+
+    assert_eq!(complete_state(&state, 2), Completion::Invalid);
+}
+
+#[test]
+fn other_sizes_invalid() {
+    assert_eq!(complete_state(&FaceletCube::new(4).state(), 10), Completion::Invalid);
+    assert_eq!(complete_state(&FaceletCube::new(1).state(), 10), Completion::Invalid);
+    assert_eq!(complete_state(&[Face::X; 50], 10), Completion::Invalid);
+    assert_eq!(complete_state(&[], 10), Completion::Invalid);
+}
@@ -0,0 +1,139 @@
+//
+// Two-Phase Solver Tests
+//
+// The tables are cached in the target directory, as generating them takes a while
+// in debug builds.
+//
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, GeoCube, TwoPhaseOptions, cache_two_phase_tables, parse_scramble, random_state, solve_two_phase};
+
+const SUPERFLIP: &str = "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2";
+
+fn cache_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("two-phase")
+}
+
+fn two_phase(cube: &impl Cube, options: &TwoPhaseOptions) -> Option<Vec<Move>> {
+    cache_two_phase_tables(&cache_dir()).unwrap();
+    solve_two_phase(cube, options)
+}
+
+fn solve(cube: &impl Cube) -> Vec<Move> {
+    two_phase(cube, &TwoPhaseOptions::default()).unwrap()
+}
+
+#[test]
+fn solved_state() {
+    assert_eq!(solve(&FaceletCube::new(3)), vec![]);
+}
+
+#[test_case("R", 1 ; "single move")]
+#[test_case("R U R' U'", 4 ; "sexy move")]
+#[test_case("F2 U2 R2 D2", 4 ; "phase two only")]
+fn short_scrambles_solved_optimally(scramble: &str, length: usize) {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(scramble)));
+    let options = TwoPhaseOptions { target_length: 0, ..TwoPhaseOptions::default() };
+    let solution = two_phase(&cube, &options).unwrap();
+
+    assert_eq!(solution.len(), length);
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test_case(0)]
+#[test_case(1)]
+#[test_case(2)]
+#[test_case(3)]
+#[test_case(4)]
+fn random_states_solved(seed: u64) {
+    let cube = random_state(seed);
+    let solution = solve(&cube);
+
+    assert!(solution.len() <= 21);
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn superflip() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(SUPERFLIP)));
+    let solution = solve(&cube);
+
+    assert!(solution.len() <= 21);
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn geometric_cube() {
+    let cube = GeoCube::new(3).apply_moves(&parse_scramble(String::from("R U F' L2 D B R' F2")));
+    assert!(cube.apply_moves(&solve(&cube)).is_solved());
+}
+
+#[test]
+fn rotated_scramble() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("x R U F' y D2 L z'")));
+    assert!(cube.apply_moves(&solve(&cube)).is_solved());
+}
+
+#[test]
+fn untracked_cube() {
+    let state = random_state(5).state();
+    assert!(FaceletCube::from(state.clone()).apply_moves(&solve(&FaceletCube::from(state))).is_solved());
+}
+
+#[test]
+fn shorter_solutions_found_with_more_time() {
+    let cube = random_state(6);
+    let first = solve(&cube);
+
+    let options = TwoPhaseOptions { target_length: 0, time_limit: Some(Duration::from_millis(500)), ..TwoPhaseOptions::default() };
+    let start = Instant::now();
+    let improved = two_phase(&cube, &options).unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(improved.len() <= first.len());
+    assert!(cube.apply_moves(&improved).is_solved());
+}
+
+#[test]
+fn length_budget_respected() {
+    let cube = random_state(7);
+    let options = TwoPhaseOptions { max_length: 20, target_length: 20, ..TwoPhaseOptions::default() };
+    let solution = two_phase(&cube, &options).unwrap();
+
+    assert!(solution.len() <= 20);
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn impossible_length_budget() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F")));
+    let options = TwoPhaseOptions { max_length: 2, target_length: 2, ..TwoPhaseOptions::default() };
+
+    assert_eq!(two_phase(&cube, &options), None);
+}
+
+#[test]
+fn unsolvable_states_rejected() {
+    let mut state = FaceletCube::new(3).state();
+    state.swap(7, 19);
+
+    assert_eq!(two_phase(&FaceletCube::from(state), &TwoPhaseOptions::default()), None);
+    assert_eq!(two_phase(&FaceletCube::new(3).mask(&|_, _| Face::X), &TwoPhaseOptions::default()), None);
+    assert_eq!(two_phase(&FaceletCube::new(4), &TwoPhaseOptions::default()), None);
+}
+
+#[test]
+fn tables_cached_on_disk() {
+    two_phase(&FaceletCube::new(3), &TwoPhaseOptions::default());
+
+    // Each table is saved with a 16 byte header.
+    assert_eq!(fs::metadata(cache_dir().join("two-phase-slice-twist.bin")).unwrap().len(), 16 + 495 * 2187);
+    assert_eq!(fs::metadata(cache_dir().join("two-phase-corner-moves.bin")).unwrap().len(), 16 + 2 * 40320 * 18);
+    assert!(cache_two_phase_tables(&cache_dir()).is_ok());
+}
@@ -0,0 +1,154 @@
+//
+// Optimal Solver Tests
+//
+// The tables are cached in the target directory, as generating them takes a while
+// in debug builds.
+//
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, GeoCube, OptimalSolver, TwoPhaseOptions};
+use cubesim::{parse_scramble, random_move_scramble, random_state, solve_two_phase};
+
+fn cache_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("optimal")
+}
+
+fn solver() -> &'static OptimalSolver {
+    static SOLVER: OnceLock<OptimalSolver> = OnceLock::new();
+    SOLVER.get_or_init(|| OptimalSolver::cached(4, &cache_dir()).unwrap())
+}
+
+#[test]
+fn solved_state() {
+    assert_eq!(solver().solve(&FaceletCube::new(3), None), Some(vec![]));
+}
+
+#[test_case("R", 1 ; "single move")]
+#[test_case("R U U' R", 1 ; "cancelling moves")]
+#[test_case("R U R' U'", 4 ; "sexy move")]
+#[test_case("R U R' U R U2 R'", 7 ; "sune")]
+fn known_distances(scramble: &str, length: usize) {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(scramble)));
+    let solution = solver().solve(&cube, None).unwrap();
+
+    assert_eq!(solution.len(), length);
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn self_symmetric_corners() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U' B")));
+    assert_eq!(solver().solve(&cube, None).unwrap().len(), 3);
+}
+
+#[test]
+fn all_three_move_scrambles() {
+    use MoveVariant::*;
+    let turns = [Move::U, Move::R, Move::F, Move::L, Move::D, Move::B]
+        .iter()
+        .flat_map(|turn| [turn(Standard), turn(Double), turn(Inverse)])
+        .collect::<Vec<_>>();
+
+    for &a in &turns {
+        for &b in &turns {
+            for &c in &turns {
+                let cube = FaceletCube::new(3).apply_moves(&[a, b, c]);
+                let solution = solver().solve(&cube, None).unwrap();
+
+                assert!(solution.len() <= 3, "{} {} {} solved in {} moves", a, b, c, solution.len());
+                assert!(cube.apply_moves(&solution).is_solved());
+            }
+        }
+    }
+}
+
+#[test_case(0)]
+#[test_case(1)]
+#[test_case(2)]
+fn no_shorter_than_exhaustive_two_phase(seed: u64) {
+    let cube = FaceletCube::new(3).apply_moves(&random_move_scramble(3, 9, seed));
+    let solution = solver().solve(&cube, None).unwrap();
+
+    // With no target length, the two-phase solver only stops once no shorter solution is left.
+    let options = TwoPhaseOptions { target_length: 0, ..TwoPhaseOptions::default() };
+    let shortest = solve_two_phase(&cube, &options).unwrap();
+
+    assert_eq!(solution.len(), shortest.len());
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn geometric_cube() {
+    let cube = GeoCube::new(3).apply_moves(&parse_scramble(String::from("F R' D2 L U")));
+    let solution = solver().solve(&cube, None).unwrap();
+
+    assert_eq!(solution.len(), 5);
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn rotated_scramble() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("x R U F' y D2 L z'")));
+    let solution = solver().solve(&cube, None).unwrap();
+
+    assert!(cube.apply_moves(&solution).is_solved());
+    assert_eq!(solution.iter().filter(|mv| !matches!(mv, Move::X(_) | Move::Y(_) | Move::Z(_))).count(), 5);
+}
+
+#[test]
+fn time_limit_respected() {
+    assert_eq!(solver().solve(&random_state(0), Some(Duration::from_millis(10))), None);
+}
+
+#[test]
+fn unsolvable_states_rejected() {
+    let mut state = FaceletCube::new(3).state();
+    state.swap(7, 19);
+
+    assert_eq!(solver().solve(&FaceletCube::from(state), None), None);
+    assert_eq!(solver().solve(&FaceletCube::new(2), None), None);
+}
+
+#[test]
+fn damaged_cache_regenerated() {
+    let damaged = cache_dir().with_file_name("optimal-damaged");
+    fs::create_dir_all(&damaged).unwrap();
+
+    // Reuse the corner table, which is the slowest to generate.
+    solver();
+    let corners = "optimal-corners.bin";
+    fs::copy(cache_dir().join(corners), damaged.join(corners)).unwrap();
+    fs::write(damaged.join("optimal-edges-3.bin"), [1, 2, 3]).unwrap();
+
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R' U'")));
+    assert_eq!(OptimalSolver::cached(3, &damaged).unwrap().solve(&cube, None).unwrap().len(), 4);
+    let edges = fs::read(damaged.join("optimal-edges-3.bin")).unwrap();
+    assert!(edges.len() > 3);
+
+    // A table of the right size whose contents do not match its checksum.
+    let mut flipped = edges.clone();
+    *flipped.last_mut().unwrap() ^= 1;
+    fs::write(damaged.join("optimal-edges-3.bin"), flipped).unwrap();
+
+    assert_eq!(OptimalSolver::cached(3, &damaged).unwrap().solve(&cube, None).unwrap().len(), 4);
+    assert_eq!(fs::read(damaged.join("optimal-edges-3.bin")).unwrap(), edges);
+}
+
+#[test]
+fn table_sizes() {
+    assert!(OptimalSolver::table_size(4) < OptimalSolver::table_size(5));
+    assert!(OptimalSolver::table_size(6) < 30_000_000);
+}
+
+#[test]
+#[should_panic]
+fn edge_groups_limited() {
+    OptimalSolver::table_size(8);
+}
@@ -0,0 +1,123 @@
+//
+// Pattern Matching Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::prelude::Face::*;
+use cubesim::{FaceletCube, GeoCube, MatchMode, parse_scramble, solved_state};
+
+const SUNE: &str = "R U R' U R U2 R'";
+const T_PERM: &str = "R U R' U' R' F R2 U' R' U' R U R' F'";
+
+/// The pattern for a solved first two layers with the U face oriented.
+fn oll_skip() -> Vec<Face> {
+    let mut pattern = solved_state(3);
+
+    for (i, face) in pattern.iter_mut().enumerate() {
+        if i % 9 < 3 && *face != U && *face != D {
+            *face = X;
+        }
+    }
+
+    pattern
+}
+
+/// The pattern of a cube after a scramble, with only the last layer shown.
+fn last_layer_case(scramble: &str) -> Vec<Face> {
+    let state = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(scramble))).state();
+
+    state
+        .iter()
+        .enumerate()
+        .map(|(i, &f)| if i < 9 || (i % 9 < 3 && !(27..36).contains(&i)) { f } else { X })
+        .collect()
+}
+
+#[test_case(GeoCube::new(3) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(3) ; "Facelet Cube")]
+fn wildcards_match_anything(cube: impl Cube) {
+    let scrambled = cube.apply_moves(&parse_scramble(String::from("R U F' L2 D B")));
+
+    assert!(scrambled.matches_pattern(&[X; 54]));
+    assert!(scrambled.matches_pattern(&scrambled.state()));
+    assert!(!scrambled.matches_pattern(&solved_state(3)));
+}
+
+#[test]
+fn masked_stickers_only_match_wildcards() {
+    let cube = FaceletCube::new(3).mask(&|_, f| if f == U { f } else { X });
+
+    assert!(!cube.matches_pattern(&solved_state(3)));
+    assert!(cube.matches_pattern(&cube.state()));
+}
+
+#[test_case("", true ; "solved")]
+#[test_case(T_PERM, true ; "pll case")]
+#[test_case(SUNE, false ; "sune")]
+#[test_case("R", false ; "first two layers broken")]
+fn oriented_last_layer(scramble: &str, matches: bool) {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(scramble)));
+    assert_eq!(cube.matches_pattern(&oll_skip()), matches);
+}
+
+#[test_case("", vec![] ; "no auf")]
+#[test_case("U", vec![Move::U(MoveVariant::Inverse)] ; "u")]
+#[test_case("U2", vec![Move::U(MoveVariant::Double)] ; "u2")]
+#[test_case("U'", vec![Move::U(MoveVariant::Standard)] ; "u prime")]
+fn matches_up_to_auf(auf: &str, transformation: Vec<Move>) {
+    let pattern = last_layer_case(SUNE);
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(format!("{} {}", SUNE, auf)));
+
+    assert_eq!(cube.match_pattern(&pattern, MatchMode::Auf), Some(transformation.clone()));
+    assert_eq!(cube.match_pattern(&pattern, MatchMode::Exact).is_some(), transformation.is_empty());
+}
+
+#[test]
+fn matches_up_to_rotation() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F' L2 D B")));
+    let pattern = cube.state();
+    let rotated = cube.apply_moves(&parse_scramble(String::from("x y'")));
+
+    assert_eq!(rotated.match_pattern(&pattern, MatchMode::Exact), None);
+    assert_eq!(rotated.match_pattern(&pattern, MatchMode::Auf), None);
+
+    let rotation = rotated.match_pattern(&pattern, MatchMode::Rotation).unwrap();
+    assert_eq!(rotated.apply_moves(&rotation).state(), pattern);
+}
+
+#[test]
+fn matches_up_to_rotation_and_auf() {
+    let pattern = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(SUNE))).state();
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(format!("{} U2 z2 y", SUNE)));
+
+    assert_eq!(cube.match_pattern(&pattern, MatchMode::Rotation), None);
+
+    let transformation = cube.match_pattern(&pattern, MatchMode::RotationAndAuf).unwrap();
+    assert!(cube.apply_moves(&transformation).matches_pattern(&pattern));
+    assert_eq!(transformation.last(), Some(&Move::U(MoveVariant::Double)));
+}
+
+#[test]
+fn first_matching_pattern_reported() {
+    let cases = ["R U R' U R U2 R'", "L' U' L U' L' U2 L", "R U2 R' U' R U' R'"]
+        .iter()
+        .map(|alg| last_layer_case(alg))
+        .collect::<Vec<_>>();
+    let patterns = cases.iter().map(|p| p.as_slice()).collect::<Vec<_>>();
+
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("L' U' L U' L' U2 L U")));
+    let (index, transformation) = cube.match_patterns(&patterns, MatchMode::Auf).unwrap();
+
+    assert_eq!(index, 1);
+    assert_eq!(transformation, vec![Move::U(MoveVariant::Inverse)]);
+    assert_eq!(FaceletCube::new(3).match_patterns(&patterns, MatchMode::RotationAndAuf), None);
+}
+
+#[test]
+fn pattern_length_must_match() {
+    assert!(!FaceletCube::new(3).matches_pattern(&solved_state(2)));
+    assert!(!FaceletCube::new(2).matches_pattern(&solved_state(3)));
+    assert_eq!(FaceletCube::new(3).match_pattern(&solved_state(2), MatchMode::RotationAndAuf), None);
+}
@@ -0,0 +1,134 @@
+//
+// Picking Tests
+//
+
+use cgmath::{InnerSpace, Vector3};
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{GeoCube, sticker_index};
+
+/// The center of each facelet together with the outward normal of its face.
+fn facelet_centers(size: i32) -> Vec<(Vector3<f64>, Vector3<f64>)> {
+    GeoCube::new(size)
+        .stickers()
+        .iter()
+        .map(|s| {
+            let center = Vector3::new(s.current.x as f64, s.current.y as f64, s.current.z as f64);
+            let normal = center.map(|c| if c.abs() == size as f64 { c.signum() } else { 0.0 });
+            (center, normal)
+        })
+        .collect()
+}
+
+#[test_case(2)]
+#[test_case(3)]
+#[test_case(4)]
+#[test_case(5)]
+fn pick_every_facelet(size: i32) {
+    let cube = GeoCube::new(size);
+
+    for (sticker, (center, normal)) in cube.stickers().iter().zip(facelet_centers(size)) {
+        let offset = Vector3::new(0.3, -0.2, 0.4).map(|c| c * 0.5);
+        let origin = center + normal * 5.0 + (offset - normal * offset.dot(normal));
+        let hit = cube.pick(origin, -normal).unwrap();
+
+        assert_eq!(hit.index, sticker.current_index());
+        assert_eq!(hit.face, sticker.current_face());
+        assert_eq!(hit.distance, 5.0);
+    }
+}
+
+#[test]
+fn pick_oblique_ray() {
+    let cube = GeoCube::new(3);
+    let hit = cube.pick(Vector3::new(10.0, 12.0, 10.0), Vector3::new(-1.0, -1.0, -1.0)).unwrap();
+
+    assert_eq!(hit.face, Face::U);
+    assert_eq!(hit.index, sticker_index(3, Face::U, 9));
+    assert_eq!(hit.point, Vector3::new(1.0, 3.0, 1.0));
+}
+
+#[test]
+fn pick_misses() {
+    let cube = GeoCube::new(3);
+
+    assert_eq!(cube.pick(Vector3::new(0.0, 0.0, 10.0), Vector3::new(0.0, 0.0, 1.0)), None);
+    assert_eq!(cube.pick(Vector3::new(0.0, 4.0, 10.0), Vector3::new(0.0, 0.0, -1.0)), None);
+    assert_eq!(cube.pick(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0)), None);
+    assert_eq!(cube.pick(Vector3::new(10.0, 0.0, 10.0), Vector3::new(-1.0, 0.0, 1.0)), None);
+}
+
+#[test]
+fn resolve_drag_face_moves() {
+    let cube = GeoCube::new(3);
+    let drag = |face, index, x, y, z| cube.resolve_drag(sticker_index(3, face, index), Vector3::new(x, y, z), false);
+
+    assert_eq!(drag(Face::F, 9, 0.0, 1.0, 0.0), Some(vec![Move::R(MoveVariant::Standard)]));
+    assert_eq!(drag(Face::F, 1, -1.0, 0.0, 0.0), Some(vec![Move::U(MoveVariant::Standard)]));
+    assert_eq!(drag(Face::U, 1, -1.0, 0.0, 0.0), Some(vec![Move::B(MoveVariant::Standard)]));
+    assert_eq!(drag(Face::R, 7, 0.0, 0.0, -1.0), Some(vec![Move::D(MoveVariant::Standard)]));
+    assert_eq!(drag(Face::F, 5, 0.0, 0.0, 1.0), None);
+}
+
+#[test]
+fn resolve_drag_index_out_of_range() {
+    let cube = GeoCube::new(3);
+    let direction = Vector3::new(0.0, 1.0, 0.0);
+
+    assert_eq!(cube.resolve_drag(-1, direction, false), None);
+    assert_eq!(cube.resolve_drag(54, direction, false), None);
+    assert!(cube.resolve_drag(53, direction, false).is_some());
+}
+
+#[test]
+fn resolve_drag_slices() {
+    let cube = GeoCube::new(5);
+    let index = sticker_index(5, Face::F, 2);
+
+    assert_eq!(
+        cube.resolve_drag(index, Vector3::new(0.0, -1.0, 0.0), false),
+        Some(vec![Move::Lw(2, MoveVariant::Standard), Move::L(MoveVariant::Inverse)])
+    );
+    assert_eq!(
+        cube.resolve_drag(index, Vector3::new(0.0, -1.0, 0.0), true),
+        Some(vec![Move::Lw(2, MoveVariant::Standard)])
+    );
+    assert_eq!(
+        cube.resolve_drag(sticker_index(5, Face::F, 3), Vector3::new(0.0, 1.0, 0.0), false),
+        Some(vec![Move::Lw(3, MoveVariant::Inverse), Move::Lw(2, MoveVariant::Standard)])
+    );
+}
+
+#[test_case(2)]
+#[test_case(3)]
+#[test_case(4)]
+#[test_case(5)]
+fn resolve_drag_turns_dragged_layer(size: i32) {
+    let cube = GeoCube::new(size);
+    let directions = [
+        Vector3::new(1.0, 0.0, 0.0), Vector3::new(-1.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, -1.0, 0.0),
+        Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.0, 0.0, -1.0),
+    ];
+
+    for (i, (center, normal)) in facelet_centers(size).into_iter().enumerate() {
+        let index = cube.stickers()[i].current_index();
+
+        for direction in directions.iter().filter(|d| d.dot(normal) == 0.0) {
+            let moves = cube.resolve_drag(index, *direction, false).unwrap();
+            let turned = cube.apply_moves(&moves).stickers();
+            let axis = (0..3).find(|&a| normal[a] == 0.0 && direction[a] == 0.0).unwrap();
+
+            let moved = turned[i].current - cube.stickers()[i].current;
+            let moved = Vector3::new(moved.x as f64, moved.y as f64, moved.z as f64);
+            assert!(moved.dot(*direction) > 0.0);
+
+            for (before, after) in cube.stickers().iter().zip(&turned) {
+                if before.current[axis].clamp(1 - size, size - 1) != center[axis] as i32 {
+                    assert_eq!(before.current, after.current);
+                }
+            }
+        }
+    }
+}
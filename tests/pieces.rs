@@ -0,0 +1,101 @@
+//
+// Piece Classification Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{GeoCube, PieceType, all_moves, piece_type, sticker_orbit, orbit_count, stickers_of, orbit_stickers};
+
+const PIECE_TYPES: [PieceType; 7] = [
+    PieceType::Center, PieceType::Corner, PieceType::Midge, PieceType::Wing,
+    PieceType::XCenter, PieceType::TCenter, PieceType::Oblique
+];
+
+#[test_case(1, &[(PieceType::Center, 6)] ; "1x1x1")]
+#[test_case(2, &[(PieceType::Corner, 24)] ; "2x2x2")]
+#[test_case(3, &[(PieceType::Center, 6), (PieceType::Corner, 24), (PieceType::Midge, 24)] ; "3x3x3")]
+#[test_case(4, &[(PieceType::Corner, 24), (PieceType::Wing, 48), (PieceType::XCenter, 24)] ; "4x4x4")]
+#[test_case(5, &[
+    (PieceType::Center, 6), (PieceType::Corner, 24), (PieceType::Midge, 24),
+    (PieceType::Wing, 48), (PieceType::XCenter, 24), (PieceType::TCenter, 24)
+] ; "5x5x5")]
+#[test_case(7, &[
+    (PieceType::Center, 6), (PieceType::Corner, 24), (PieceType::Midge, 24), (PieceType::Wing, 96),
+    (PieceType::XCenter, 48), (PieceType::TCenter, 48), (PieceType::Oblique, 48)
+] ; "7x7x7")]
+fn piece_counts(size: i32, expected: &[(PieceType, usize)]) {
+    for piece_type in PIECE_TYPES {
+        let count = expected.iter().find(|(t, _)| *t == piece_type).map_or(0, |(_, n)| *n);
+        assert_eq!(stickers_of(size, piece_type).len(), count, "{:?}", piece_type);
+    }
+}
+
+#[test_case(3)]
+#[test_case(4)]
+#[test_case(5)]
+#[test_case(6)]
+#[test_case(7)]
+#[test_case(8)]
+fn every_sticker_classified_once(size: i32) {
+    let mut stickers = PIECE_TYPES
+        .iter()
+        .flat_map(|&t| (0..orbit_count(size, t)).flat_map(move |o| orbit_stickers(size, t, o)))
+        .collect::<Vec<_>>();
+
+    stickers.sort_unstable();
+    assert_eq!(stickers, (0..6 * size * size).collect::<Vec<_>>());
+}
+
+#[test_case(4, PieceType::Wing, 1)]
+#[test_case(6, PieceType::Wing, 2)]
+#[test_case(6, PieceType::XCenter, 2)]
+#[test_case(6, PieceType::Oblique, 2)]
+#[test_case(7, PieceType::TCenter, 2)]
+#[test_case(8, PieceType::Oblique, 6)]
+fn orbit_counts(size: i32, piece_type: PieceType, count: i32) {
+    assert_eq!(orbit_count(size, piece_type), count);
+
+    for orbit in 0..count {
+        let stickers = orbit_stickers(size, piece_type, orbit);
+        let expected = if piece_type == PieceType::Wing { 48 } else { 24 };
+
+        assert_eq!(stickers.len(), expected);
+        assert!(stickers.iter().all(|&i| sticker_orbit(size, i) == orbit));
+    }
+}
+
+#[test_case(2)]
+#[test_case(3)]
+#[test_case(4)]
+#[test_case(5)]
+#[test_case(6)]
+#[test_case(7)]
+fn moves_preserve_type_and_orbit(size: i32) {
+    for mv in all_moves(size) {
+        for sticker in GeoCube::new(size).apply_move(mv).stickers() {
+            let from = sticker.set_solved().current_index();
+            let to = sticker.current_index();
+
+            assert_eq!(piece_type(size, from), piece_type(size, to), "{} moves {} to {}", mv, from, to);
+            assert_eq!(sticker_orbit(size, from), sticker_orbit(size, to), "{} moves {} to {}", mv, from, to);
+        }
+    }
+}
+
+#[test]
+fn oblique_orbits_are_mirror_images() {
+    // On a 6x6x6 the obliques next to the top left x-center sit on either side of the diagonal.
+    let left = cubesim::sticker_index(6, Face::U, 9);
+    let right = cubesim::sticker_index(6, Face::U, 14);
+
+    assert_eq!(piece_type(6, left), PieceType::Oblique);
+    assert_eq!(piece_type(6, right), PieceType::Oblique);
+    assert_ne!(sticker_orbit(6, left), sticker_orbit(6, right));
+}
+
+#[test]
+#[should_panic]
+fn piece_type_of_missing_sticker() {
+    piece_type(3, 54);
+}
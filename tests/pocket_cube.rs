@@ -0,0 +1,91 @@
+//
+// 2x2x2 Solver Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, GeoCube, Metric, distance_2x2, parse_scramble, random_move_scramble, solve_2x2};
+
+fn is_quarter_turn(mv: &Move) -> bool {
+    !matches!(mv, Move::U(MoveVariant::Double) | Move::R(MoveVariant::Double) | Move::F(MoveVariant::Double))
+}
+
+#[test_case(Metric::HalfTurn)]
+#[test_case(Metric::QuarterTurn)]
+fn solved_state(metric: Metric) {
+    assert_eq!(solve_2x2(&FaceletCube::new(2), metric), Some(vec![]));
+    assert_eq!(distance_2x2(&FaceletCube::new(2), metric), Some(0));
+}
+
+#[test_case("R", 1, 1 ; "single move")]
+#[test_case("R2", 1, 2 ; "half turn")]
+#[test_case("L", 1, 1 ; "moving the fixed corner")]
+#[test_case("R U R' U'", 4, 4 ; "sexy move")]
+#[test_case("R2 U2", 2, 4 ; "two half turns")]
+fn known_distances(scramble: &str, half_turns: usize, quarter_turns: usize) {
+    let cube = FaceletCube::new(2).apply_moves(&parse_scramble(String::from(scramble)));
+
+    assert_eq!(distance_2x2(&cube, Metric::HalfTurn), Some(half_turns));
+    assert_eq!(distance_2x2(&cube, Metric::QuarterTurn), Some(quarter_turns));
+}
+
+#[test_case(0)]
+#[test_case(1)]
+#[test_case(2)]
+#[test_case(3)]
+fn scrambles_solved_optimally(seed: u64) {
+    let cube = FaceletCube::new(2).apply_moves(&random_move_scramble(2, 25, seed));
+
+    for metric in [Metric::HalfTurn, Metric::QuarterTurn] {
+        let solution = solve_2x2(&cube, metric).unwrap();
+        let moves = solution.iter().filter(|mv| !matches!(mv, Move::X(_) | Move::Y(_) | Move::Z(_))).count();
+
+        assert!(cube.apply_moves(&solution).is_solved());
+        assert_eq!(Some(moves), distance_2x2(&cube, metric));
+    }
+
+    let half_turns = distance_2x2(&cube, Metric::HalfTurn).unwrap();
+    let quarter_turns = distance_2x2(&cube, Metric::QuarterTurn).unwrap();
+    assert!(half_turns <= 11 && quarter_turns <= 14);
+    assert!(half_turns <= quarter_turns && quarter_turns <= 2 * half_turns);
+}
+
+#[test]
+fn quarter_turn_solutions_avoid_half_turns() {
+    let cube = FaceletCube::new(2).apply_moves(&parse_scramble(String::from("R2 U2 F2")));
+    let solution = solve_2x2(&cube, Metric::QuarterTurn).unwrap();
+
+    assert_eq!(solution.len(), 6);
+    assert!(solution.iter().all(is_quarter_turn));
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn geometric_cube() {
+    let cube = GeoCube::new(2).apply_moves(&parse_scramble(String::from("R U' F2 U R'")));
+    let solution = solve_2x2(&cube, Metric::HalfTurn).unwrap();
+
+    assert_eq!(solution.len(), 5);
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn rotated_cube() {
+    let cube = FaceletCube::new(2).apply_moves(&parse_scramble(String::from("x R U y' F2 z")));
+    let solution = solve_2x2(&cube, Metric::HalfTurn).unwrap();
+
+    assert!(cube.apply_moves(&solution).is_solved());
+    assert_eq!(distance_2x2(&cube, Metric::HalfTurn), Some(3));
+}
+
+#[test]
+fn unsolvable_states_rejected() {
+    let mut state = FaceletCube::new(2).state();
+    state.swap(3, 9);
+
+    assert_eq!(solve_2x2(&FaceletCube::from(state.clone()), Metric::HalfTurn), None);
+    assert_eq!(distance_2x2(&FaceletCube::from(state), Metric::HalfTurn), None);
+    assert_eq!(distance_2x2(&FaceletCube::new(2).mask(&|_, _| Face::X), Metric::HalfTurn), None);
+    assert_eq!(solve_2x2(&FaceletCube::new(3), Metric::HalfTurn), None);
+}
@@ -0,0 +1,140 @@
+//
+// Reduction Solver Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, GeoCube, sticker_index, parse_scramble, random_move_scramble, solve_4x4, solve_nxn};
+
+fn scrambled(scramble: &str) -> FaceletCube {
+    FaceletCube::new(4).apply_moves(&parse_scramble(String::from(scramble)))
+}
+
+//
+// 4x4x4 Tests
+//
+#[test]
+fn solved_state() {
+    assert_eq!(solve_4x4(&FaceletCube::new(4)), Some(vec![]));
+}
+
+#[test_case("Rw R'" ; "oll parity")]
+#[test_case("R" ; "pll parity")]
+#[test_case("Rw" ; "both parities")]
+#[test_case("Rw2 U2 Rw2 Uw2 Rw2 Uw2" ; "swapped edges")]
+#[test_case("Uw R2 Fw' D Bw2 L' Rw U2" ; "short scramble")]
+fn scrambles_solved(scramble: &str) {
+    let cube = scrambled(scramble);
+    assert!(cube.apply_moves(&solve_4x4(&cube).unwrap()).is_solved());
+}
+
+#[test_case(0)]
+#[test_case(1)]
+#[test_case(2)]
+#[test_case(3)]
+#[test_case(4)]
+fn random_scrambles_solved(seed: u64) {
+    let cube = FaceletCube::new(4).apply_moves(&random_move_scramble(4, 60, seed));
+    assert!(cube.apply_moves(&solve_4x4(&cube).unwrap()).is_solved());
+}
+
+#[test]
+fn solution_in_wca_notation() {
+    let cube = FaceletCube::new(4).apply_moves(&random_move_scramble(4, 60, 5));
+    let solution = solve_4x4(&cube).unwrap();
+    let notation = solution.iter().map(Move::to_string).collect::<Vec<_>>().join(" ");
+
+    assert_eq!(parse_scramble(notation), solution);
+    assert!(solution.iter().all(|mv| match mv {
+        Move::Uw(n, _) | Move::Lw(n, _) | Move::Fw(n, _) | Move::Rw(n, _) | Move::Bw(n, _) | Move::Dw(n, _) => *n == 2,
+        _ => true,
+    }));
+}
+
+#[test]
+fn rotated_scramble() {
+    let cube = scrambled("x Rw U F' y Dw2 L z'");
+    assert!(cube.apply_moves(&solve_4x4(&cube).unwrap()).is_solved());
+}
+
+#[test]
+fn geometric_cube() {
+    let cube = GeoCube::new(4).apply_moves(&parse_scramble(String::from("Rw U2 Fw' R D Lw2 Bw")));
+    assert!(cube.apply_moves(&solve_4x4(&cube).unwrap()).is_solved());
+}
+
+#[test]
+fn untracked_cube() {
+    let state = FaceletCube::new(4).apply_moves(&random_move_scramble(4, 60, 6)).state();
+    let solution = solve_4x4(&FaceletCube::from(state.clone())).unwrap();
+
+    assert!(FaceletCube::from(state).apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn unsolvable_states_rejected() {
+    // Twist a corner in place.
+    let mut state = FaceletCube::new(4).state();
+    let (u, r, f) = (15, 16, 35);
+    (state[u], state[r], state[f]) = (state[f], state[u], state[r]);
+
+    assert_eq!(solve_4x4(&FaceletCube::from(state)), None);
+    assert_eq!(solve_4x4(&FaceletCube::new(4).mask(&|_, _| Face::X)), None);
+    assert_eq!(solve_4x4(&FaceletCube::new(5)), None);
+}
+
+//
+// NxN Tests
+//
+#[test_case(4)]
+#[test_case(5)]
+#[test_case(6)]
+#[test_case(7)]
+#[test_case(8)]
+#[test_case(9)]
+#[test_case(10)]
+fn nxn_random_scrambles_solved(size: i32) {
+    let cube = FaceletCube::new(size).apply_moves(&random_move_scramble(size, 100, size as u64));
+    let solution = solve_nxn(&cube).unwrap();
+    let notation = solution.iter().map(Move::to_string).collect::<Vec<_>>().join(" ");
+
+    assert!(cube.apply_moves(&solution).is_solved());
+    assert_eq!(parse_scramble(notation), solution);
+}
+
+#[test_case(5)]
+#[test_case(6)]
+#[test_case(7)]
+fn nxn_solved_state(size: i32) {
+    assert_eq!(solve_nxn(&FaceletCube::new(size)), Some(vec![]));
+}
+
+#[test_case(5, "Rw R'" ; "oll parity")]
+#[test_case(5, "3Rw Rw'" ; "middle slice")]
+#[test_case(6, "3Rw Rw'" ; "inner oll parity")]
+#[test_case(6, "R" ; "pll parity")]
+#[test_case(7, "Rw R' 3Uw Uw'" ; "two orbits")]
+fn nxn_scrambles_solved(size: i32, scramble: &str) {
+    let cube = FaceletCube::new(size).apply_moves(&parse_scramble(String::from(scramble)));
+    assert!(cube.apply_moves(&solve_nxn(&cube).unwrap()).is_solved());
+}
+
+#[test]
+fn nxn_geometric_cube() {
+    let cube = GeoCube::new(5).apply_moves(&parse_scramble(String::from("Rw U2 3Fw' R D Lw2 Bw")));
+    assert!(cube.apply_moves(&solve_nxn(&cube).unwrap()).is_solved());
+}
+
+#[test]
+fn nxn_unsolvable_states_rejected() {
+    // Swap two midges.
+    let mut state = FaceletCube::new(5).state();
+    state.swap(sticker_index(5, Face::U, 23) as usize, sticker_index(5, Face::U, 15) as usize);
+    state.swap(sticker_index(5, Face::F, 3) as usize, sticker_index(5, Face::R, 3) as usize);
+
+    assert_eq!(solve_nxn(&FaceletCube::from(state)), None);
+    assert_eq!(solve_nxn(&FaceletCube::new(3)), None);
+    assert_eq!(solve_nxn(&FaceletCube::new(2)), None);
+    assert_eq!(solve_nxn(&FaceletCube::new(11)), None);
+}
@@ -0,0 +1,476 @@
+//
+// Rendering Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::prelude::Face::*;
+use cubesim::{FaceletCube, Color, ColorScheme, terminal_net};
+use cubesim::{Arrow, SvgOptions, svg_net, svg_isometric};
+use cubesim::{svg_last_layer, svg_last_layer_case, last_layer_arrows};
+use cubesim::{PngOptions, png_net, png_isometric};
+use cubesim::{GeoCube, obj_mesh, gltf_mesh};
+use cubesim::{AnimationOptions, svg_animation};
+use cubesim::{sticker_index, parse_scramble, invert_moves};
+
+//
+// Terminal Tests
+//
+#[test]
+fn terminal_net_letters() {
+    let cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+
+    assert_eq!(terminal_net(&cube, None), [
+        "      U U F",
+        "      U U F",
+        "      U U F",
+        "L L L F F D R R R U B B",
+        "L L L F F D R R R U B B",
+        "L L L F F D R R R U B B",
+        "      D D B",
+        "      D D B",
+        "      D D B",
+    ].join("\n") + "\n");
+}
+
+#[test]
+fn terminal_net_masked() {
+    let cube = FaceletCube::new(3).mask(&|i, f| if i == sticker_index(3, U, 5) { f } else { X });
+    let net = terminal_net(&cube, None);
+
+    assert_eq!(net.lines().nth(1).unwrap(), "      X U X");
+    assert_eq!(net.lines().nth(4).unwrap(), "X X X X X X X X X X X X");
+}
+
+#[test]
+fn terminal_net_ansi() {
+    let cube = FaceletCube::new(2).mask(&|_, f| if f == U { f } else { X });
+    let net = terminal_net(&cube, Some(&ColorScheme::western()));
+
+    assert_eq!(net.lines().count(), 6);
+    assert_eq!(net.lines().next().unwrap(), "    \x1b[48;2;255;255;255m  \x1b[0m\x1b[48;2;255;255;255m  \x1b[0m");
+    assert!(net.lines().nth(2).unwrap().starts_with("\x1b[48;2;128;128;128m  \x1b[0m"));
+}
+
+#[test]
+fn facelet_cube_display() {
+    let cube = FaceletCube::new(4).apply_move(Move::Rw(2, MoveVariant::Standard));
+    assert_eq!(format!("{}", cube), terminal_net(&cube, None));
+}
+
+//
+// SVG Tests
+//
+#[test]
+fn svg_net_sticker_colors() {
+    let cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+    let svg = svg_net(&cube, &SvgOptions::default());
+
+    assert_eq!(svg.matches("<rect").count(), 54);
+    assert_eq!(svg.matches("fill=\"#ffffff\"").count(), 9);
+    assert_eq!(svg.matches("fill=\"#009b48\"").count(), 9);
+}
+
+#[test]
+fn svg_net_masked_stickers() {
+    let cube = FaceletCube::new(4).mask(&|_, f| if f == U { f } else { X });
+    let svg = svg_net(&cube, &SvgOptions::default());
+
+    assert_eq!(svg.matches("fill=\"#808080\"").count(), 80);
+}
+
+#[test]
+fn svg_custom_colors() {
+    let options = SvgOptions {
+        scheme: ColorScheme::western().with_color(U, Color::new("pink", 'P', [255, 105, 180])),
+        ..SvgOptions::default()
+    };
+
+    assert_eq!(svg_net(&FaceletCube::new(2), &options).matches("fill=\"#ff69b4\"").count(), 4);
+    assert_eq!(svg_isometric(&FaceletCube::new(2), &options).matches("fill=\"#ff69b4\"").count(), 4);
+}
+
+#[test]
+fn svg_isometric_shows_visible_faces() {
+    let svg = svg_isometric(&FaceletCube::new(5), &SvgOptions::default());
+
+    assert_eq!(svg.matches("<polygon").count(), 3 + 75);
+    assert!(!svg.contains("fill=\"#ffd500\""));
+    assert!(!svg.contains("fill=\"#0046ad\""));
+}
+
+#[test]
+fn svg_arrows() {
+    let options = SvgOptions {
+        arrows: vec![
+            Arrow::new(sticker_index(3, U, 1), sticker_index(3, U, 9)),
+            Arrow { from: sticker_index(3, F, 1), to: sticker_index(3, D, 1), double_headed: true },
+        ],
+        ..SvgOptions::default()
+    };
+
+    let net = svg_net(&FaceletCube::new(3), &options);
+    assert_eq!(net.matches("<line").count(), 2);
+    assert_eq!(net.matches("marker-start").count(), 1);
+
+    let isometric = svg_isometric(&FaceletCube::new(3), &options);
+    assert_eq!(isometric.matches("<line").count(), 1);
+}
+
+#[test]
+fn svg_arrowhead_id_prefix() {
+    let options = SvgOptions {
+        arrows: vec![Arrow::new(sticker_index(3, U, 1), sticker_index(3, U, 9))],
+        id_prefix: String::from("first-"),
+        ..SvgOptions::default()
+    };
+
+    let svg = svg_net(&FaceletCube::new(3), &options);
+    assert_eq!(svg.matches("id=\"first-arrowhead\"").count(), 1);
+    assert!(svg.contains("marker-end=\"url(#first-arrowhead)\""));
+    assert!(!svg_net(&FaceletCube::new(3), &SvgOptions::default()).contains("<marker"));
+}
+
+#[test]
+fn svg_is_deterministic() {
+    let cube = FaceletCube::new(3).apply_moves(&[Move::R(MoveVariant::Standard), Move::U(MoveVariant::Inverse)]);
+
+    assert_eq!(svg_net(&cube, &SvgOptions::default()), svg_net(&cube.clone(), &SvgOptions::default()));
+    assert_eq!(svg_isometric(&cube, &SvgOptions::default()), svg_isometric(&cube.clone(), &SvgOptions::default()));
+}
+
+//
+// Last Layer Tests
+//
+#[test]
+fn last_layer_solved_has_no_arrows() {
+    let svg = svg_last_layer(&FaceletCube::new(3), &SvgOptions::default(), true);
+
+    assert_eq!(svg.matches("<rect").count(), 21);
+    assert_eq!(svg.matches("<line").count(), 0);
+}
+
+#[test]
+fn last_layer_t_perm_arrows() {
+    let t_perm = parse_scramble(String::from("R U R' U' R' F R2 U' R' U' R U R' F'"));
+    let arrows = last_layer_arrows(&FaceletCube::new(3).apply_moves(&t_perm));
+
+    assert_eq!(arrows.len(), 2);
+    assert!(arrows.contains(&Arrow { from: sticker_index(3, U, 9), to: sticker_index(3, U, 3), double_headed: true }));
+    assert!(arrows.contains(&Arrow { from: sticker_index(3, U, 6), to: sticker_index(3, U, 4), double_headed: true }));
+}
+
+#[test]
+fn last_layer_scanned_arrows() {
+    let t_perm = parse_scramble(String::from("R U R' U' R' F R2 U' R' U' R U R' F'"));
+    let cube = FaceletCube::new(3).apply_moves(&t_perm);
+    let letters = cube.state_colors(&ColorScheme::western()).iter().map(|c| c.letter).collect::<String>();
+    let expected = last_layer_arrows(&cube);
+
+    assert_eq!(expected.len(), 2);
+    assert_eq!(last_layer_arrows(&ColorScheme::western().parse_state(&letters).unwrap()), expected);
+    assert_eq!(last_layer_arrows(&FaceletCube::from(cube.state())), expected);
+}
+
+#[test]
+fn last_layer_rotated_arrows() {
+    let u_perm = parse_scramble(String::from("R U' R U R U R U' R' U' R2"));
+    let cube = FaceletCube::new(3).apply_moves(&u_perm);
+    let rotated = FaceletCube::from(cube.apply_move(Move::Y(MoveVariant::Standard)).state());
+
+    assert_eq!(last_layer_arrows(&rotated).len(), 3);
+    assert_eq!(last_layer_arrows(&FaceletCube::new(3).mask(&|_, _| Face::X)), vec![]);
+}
+
+#[test]
+fn last_layer_u_perm_arrows() {
+    let u_perm = parse_scramble(String::from("R U' R U R U R U' R' U' R2"));
+    let arrows = last_layer_arrows(&FaceletCube::new(3).apply_moves(&u_perm));
+
+    assert_eq!(arrows.len(), 3);
+    assert!(arrows.iter().all(|a| !a.double_headed));
+
+    for arrow in &arrows {
+        assert_eq!(arrows.iter().filter(|a| a.from == arrow.to).count(), 1);
+    }
+}
+
+#[test]
+fn last_layer_case_shows_inverse() {
+    let sune = parse_scramble(String::from("R U R' U R U2 R'"));
+    let case = svg_last_layer_case(&sune, &SvgOptions::default(), false);
+    let anti_sune = svg_last_layer(&FaceletCube::new(3).apply_moves(&invert_moves(&sune)), &SvgOptions::default(), false);
+
+    assert_eq!(case, anti_sune);
+    assert_eq!(case.matches("<line").count(), 0);
+    assert_eq!(case.matches("fill=\"#ffffff\"").count(), 9);
+}
+
+//
+// PNG Tests
+//
+fn png_dimensions(png: &[u8]) -> (u32, u32) {
+    assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+    assert_eq!(&png[12..16], b"IHDR");
+
+    (
+        u32::from_be_bytes([png[16], png[17], png[18], png[19]]),
+        u32::from_be_bytes([png[20], png[21], png[22], png[23]]),
+    )
+}
+
+/// Decodes the RGBA pixels of a PNG image, inflating its image data with a reference
+/// implementation of zlib.
+fn png_pixels(png: &[u8]) -> Vec<Vec<[u8; 4]>> {
+    let (width, _) = png_dimensions(png);
+    assert_eq!(&png[24..29], &[8, 6, 0, 0, 0]);
+
+    let mut data = vec![];
+    let mut offset = 8;
+
+    while offset < png.len() {
+        let length = u32::from_be_bytes([png[offset], png[offset + 1], png[offset + 2], png[offset + 3]]) as usize;
+        if &png[offset + 4..offset + 8] == b"IDAT" {
+            data.extend(&png[offset + 8..offset + 8 + length]);
+        }
+        offset += length + 12;
+    }
+
+    let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&data).unwrap();
+
+    raw.chunks(4 * width as usize + 1)
+        .map(|row| {
+            assert_eq!(row[0], 0);
+            row[1..].chunks(4).map(|p| [p[0], p[1], p[2], p[3]]).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn png_net_dimensions() {
+    assert_eq!(png_dimensions(&png_net(&FaceletCube::new(3), &PngOptions::default())), (248, 188));
+    assert_eq!(png_dimensions(&png_net(&FaceletCube::new(5), &PngOptions { sticker_size: 10, ..PngOptions::default() })), (208, 158));
+}
+
+#[test]
+fn png_isometric_dimensions() {
+    assert_eq!(png_dimensions(&png_isometric(&FaceletCube::new(3), &PngOptions::default())), (112, 128));
+}
+
+#[test]
+fn png_net_pixels() {
+    let cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+    let pixels = png_pixels(&png_net(&cube, &PngOptions::default()));
+    let rgba = |face: Face| {
+        let [r, g, b] = ColorScheme::default().color(face).rgb;
+        [r, g, b, 255]
+    };
+
+    assert_eq!((pixels.len(), pixels[0].len()), (188, 248));
+    assert_eq!(pixels[0][0], [0, 0, 0, 0]);
+    assert_eq!(pixels[4][64], [0, 0, 0, 255]);
+
+    // The U face is drawn above the F face, three stickers from the left.
+    assert_eq!(pixels[14][74], rgba(U));
+    assert_eq!(pixels[14][114], rgba(F));
+    assert_eq!(pixels[74][114], rgba(D));
+    assert_eq!(pixels[134][74], rgba(D));
+}
+
+#[test]
+fn png_isometric_pixels() {
+    let pixels = png_pixels(&png_isometric(&FaceletCube::new(3), &PngOptions::default()));
+
+    assert_eq!((pixels.len(), pixels[0].len()), (128, 112));
+    assert_eq!(pixels[0][0], [0, 0, 0, 0]);
+    assert!(pixels.iter().flatten().any(|p| p[..3] == ColorScheme::default().color(R).rgb));
+    assert!(!pixels.iter().flatten().any(|p| p[..3] == ColorScheme::default().color(L).rgb));
+}
+
+#[test]
+fn png_ends_with_iend() {
+    let png = png_net(&FaceletCube::new(2), &PngOptions::default());
+    assert_eq!(&png[png.len() - 12..], &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]);
+}
+
+#[test]
+fn png_is_deterministic() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R' U'")));
+
+    assert_eq!(png_net(&cube, &PngOptions::default()), png_net(&cube.clone(), &PngOptions::default()));
+    assert_eq!(png_isometric(&cube, &PngOptions::default()), png_isometric(&cube.clone(), &PngOptions::default()));
+}
+
+#[test]
+fn png_depends_on_colors() {
+    let cube = FaceletCube::new(3);
+    let japanese = PngOptions { scheme: ColorScheme::japanese(), ..PngOptions::default() };
+
+    assert_ne!(png_net(&cube, &PngOptions::default()), png_net(&cube, &japanese));
+    assert_ne!(png_net(&cube, &PngOptions::default()), png_net(&cube.apply_move(Move::U(MoveVariant::Standard)), &PngOptions::default()));
+}
+
+//
+// Mesh Tests
+//
+fn obj_vertices_by_material(obj: &str) -> Vec<(String, Vec<[f64; 3]>)> {
+    let vertices = obj
+        .lines()
+        .filter_map(|line| line.strip_prefix("v "))
+        .map(|line| {
+            let v = line.split(' ').map(|c| c.parse::<f64>().unwrap()).collect::<Vec<_>>();
+            [v[0], v[1], v[2]]
+        })
+        .collect::<Vec<_>>();
+
+    let mut groups: Vec<(String, Vec<[f64; 3]>)> = vec![];
+
+    for line in obj.lines() {
+        if let Some(name) = line.strip_prefix("usemtl ") {
+            groups.push((String::from(name), vec![]));
+        } else if let Some(face) = line.strip_prefix("f ") {
+            for corner in face.split(' ') {
+                let index = corner.split("//").next().unwrap().parse::<usize>().unwrap();
+                groups.last_mut().unwrap().1.push(vertices[index - 1]);
+            }
+        }
+    }
+
+    groups
+}
+
+#[test]
+fn obj_mesh_counts() {
+    for size in [1, 2, 3, 4] {
+        let mesh = obj_mesh(&GeoCube::new(size), &ColorScheme::default(), "cube.mtl");
+        let quads = 2 * 6 * size as usize * size as usize;
+
+        assert_eq!(mesh.obj.lines().filter(|l| l.starts_with("v ")).count(), 4 * quads);
+        assert_eq!(mesh.obj.lines().filter(|l| l.starts_with("vn ")).count(), quads);
+        assert_eq!(mesh.obj.lines().filter(|l| l.starts_with("f ")).count(), quads);
+        assert_eq!(mesh.mtl.matches("newmtl").count(), 7);
+    }
+}
+
+#[test]
+fn obj_mesh_follows_moves() {
+    let cube = GeoCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+    let groups = obj_vertices_by_material(&obj_mesh(&cube, &ColorScheme::default(), "cube.mtl").obj);
+    let green = &groups.iter().find(|(name, _)| name == "green").unwrap().1;
+
+    let on_top = green.chunks(4).filter(|quad| quad.iter().all(|v| (v[1] - 1.51).abs() < 1e-9)).count();
+    assert_eq!(on_top, 3);
+    assert!(green.iter().all(|v| v.iter().all(|c| c.abs() <= 1.51)));
+}
+
+#[test]
+fn obj_mesh_bodies_on_surface() {
+    let cube = GeoCube::new(3).apply_moves(&parse_scramble(String::from("R U F'")));
+    let groups = obj_vertices_by_material(&obj_mesh(&cube, &ColorScheme::default(), "cube.mtl").obj);
+    let black = &groups.iter().find(|(name, _)| name == "black").unwrap().1;
+
+    assert_eq!(black.len(), 4 * 54);
+    assert!(black.chunks(4).all(|quad| (0..3).any(|axis| quad.iter().all(|v| (v[axis].abs() - 1.5).abs() < 1e-9))));
+}
+
+#[test]
+fn obj_mesh_masked_stickers() {
+    let cube = GeoCube::new(3).mask(&|_, f| if f == U { f } else { X });
+    let mesh = obj_mesh(&cube, &ColorScheme::default(), "cube.mtl");
+    let groups = obj_vertices_by_material(&mesh.obj);
+
+    assert_eq!(groups.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), vec!["black", "white", "gray"]);
+    assert_eq!(groups[2].1.len(), 4 * 45);
+}
+
+#[test]
+fn gltf_mesh_buffer() {
+    let gltf = gltf_mesh(&GeoCube::new(3), &ColorScheme::default());
+    let quads: usize = 2 * 54;
+    let byte_length = quads * 4 * (12 + 12) + quads * 6 * 4;
+
+    assert!(gltf.starts_with("{\"asset\":{\"version\":\"2.0\""));
+    assert!(gltf.contains(&format!("\"byteLength\":{},\"uri\":\"data:application/octet-stream;base64,", byte_length)));
+    assert_eq!(gltf.matches("\"POSITION\"").count(), 7);
+
+    let data = gltf.split("base64,").nth(1).unwrap().split('"').next().unwrap();
+    assert_eq!(data.len(), byte_length.div_ceil(3) * 4);
+}
+
+#[test]
+fn mesh_material_names_escaped() {
+    let scheme = ColorScheme::western().with_color(U, Color::new("my \"red\"\\ #1", 'M', [200, 0, 0]));
+
+    let mesh = obj_mesh(&GeoCube::new(3), &scheme, "cube.mtl");
+    assert!(mesh.mtl.contains("newmtl my_\"red\"\\__1\n"));
+    assert!(mesh.obj.contains("usemtl my_\"red\"\\__1\n"));
+
+    let gltf = gltf_mesh(&GeoCube::new(3), &scheme);
+    assert!(gltf.contains("{\"name\":\"my \\\"red\\\"\\\\ #1\","));
+
+    let json: serde_json::Value = serde_json::from_str(&gltf).unwrap();
+    assert!(json["materials"].as_array().unwrap().iter().any(|m| m["name"] == "my \"red\"\\ #1"));
+}
+
+//
+// Animation Tests
+//
+#[test]
+fn svg_animation_frames() {
+    let moves = parse_scramble(String::from("R U R' U'"));
+    let options = AnimationOptions { frames_per_move: 5, ..AnimationOptions::default() };
+    let svg = svg_animation(&GeoCube::new(3), &moves, &options);
+
+    assert_eq!(svg.matches("<g visibility=\"hidden\">").count(), 1 + 4 * 5 + 1);
+    assert_eq!(svg.matches("dur=\"4s\"").count(), 1 + 4 * 5 + 1 + 4);
+    assert!(svg.contains("<animate attributeName=\"visibility\" values=\"visible;hidden\" keyTimes=\"0;0.25\""));
+    assert!(svg.contains("<animate attributeName=\"visibility\" values=\"hidden;visible\" keyTimes=\"0;0.75\""));
+}
+
+#[test]
+fn svg_animation_captions() {
+    let moves = parse_scramble(String::from("Rw2 U' x"));
+    let svg = svg_animation(&GeoCube::new(4), &moves, &AnimationOptions::default());
+
+    assert!(svg.contains("Rw2 (1/3)</text>"));
+    assert!(svg.contains("U' (2/3)</text>"));
+    assert!(svg.contains("x (3/3)</text>"));
+
+    let options = AnimationOptions { captions: false, ..AnimationOptions::default() };
+    assert!(!svg_animation(&GeoCube::new(4), &moves, &options).contains("<text"));
+}
+
+#[test]
+fn svg_animation_speed() {
+    let moves = parse_scramble(String::from("R U"));
+    let options = AnimationOptions { move_duration: 1.5, pause: 0.5, ..AnimationOptions::default() };
+
+    assert!(svg_animation(&GeoCube::new(3), &moves, &options).contains("dur=\"4s\""));
+}
+
+#[test]
+fn svg_animation_shows_stickers() {
+    let scramble = parse_scramble(String::from("R U F'"));
+    let cube = GeoCube::new(3).apply_moves(&scramble);
+    let options = AnimationOptions { frames_per_move: 2, ..AnimationOptions::default() };
+    let svg = svg_animation(&cube, &invert_moves(&scramble), &options);
+
+    let frames = svg.split("<g visibility").skip(1).collect::<Vec<_>>();
+    let stickers = |frame: &str| frame.matches("<polygon").count() - frame.matches("fill=\"#000000\"").count();
+
+    assert_eq!(frames.len(), 1 + 3 * 2 + 1);
+    assert_eq!(stickers(frames[0]), 27);
+    assert_eq!(stickers(frames[7]), 27);
+    assert_eq!(frames[7].matches("fill=\"#ffffff\"").count(), 9);
+}
+
+#[test]
+fn svg_animation_without_moves() {
+    let svg = svg_animation(&GeoCube::new(2), &[], &AnimationOptions::default());
+
+    assert_eq!(svg.matches("<g visibility").count(), 1);
+    assert!(svg.contains("values=\"visible\" keyTimes=\"0\""));
+
+    let still = svg_animation(&GeoCube::new(2), &[], &AnimationOptions { pause: 0.0, ..AnimationOptions::default() });
+    assert!(!still.contains("<animate"));
+    assert_eq!(still.matches("<polygon").count(), 3 + 12);
+}
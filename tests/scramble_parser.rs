@@ -2,7 +2,7 @@
 // Move Parser Tests
 //
 
-use cubesim::{parse_scramble, simplify_moves};
+use cubesim::{parse_scramble, simplify_moves, all_moves};
 use cubesim::prelude::{Move::*, MoveVariant::*};
 
 #[test]
@@ -94,7 +94,13 @@ fn test_simplify_standard_and_inverse_cancel() {
 #[test]
 fn test_simplify_two_doubles_cancel() {
     assert_eq!(simplify_moves(&parse_scramble(String::from("D2 D2"))), vec![]);
-    assert_eq!(simplify_moves(&parse_scramble(String::from("2Lw 2Lw"))), vec![]);
+    assert_eq!(simplify_moves(&parse_scramble(String::from("2Lw2 2Lw2"))), vec![]);
+}
+
+#[test]
+fn test_simplify_wide_layer_count_is_not_a_double() {
+    assert_eq!(parse_scramble(String::from("2Lw")), vec![Lw(2, Standard)]);
+    assert_eq!(simplify_moves(&parse_scramble(String::from("2Lw 2Lw"))), vec![Lw(2, Double)]);
 }
 
 #[test]
@@ -128,4 +134,26 @@ fn test_simplify_long() {
 fn test_simplify_complex() {
     assert_eq!(simplify_moves(&parse_scramble(String::from("R U2 R' R U2 F F' U2 B2 B2 U' U2 U R'"))), 
                vec![])
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_display_round_trip() {
+    for mv in all_moves(6) {
+        assert_eq!(parse_scramble(mv.to_string()), vec![mv]);
+    }
+
+    let scramble = "R U' Fw2 3Lw' x2 y' z";
+    let moves = parse_scramble(String::from(scramble));
+    assert_eq!(moves.iter().map(|mv| mv.to_string()).collect::<Vec<_>>().join(" "), scramble);
+}
+
+#[test]
+fn test_multi_digit_wide_moves() {
+    assert_eq!(parse_scramble(String::from("10Rw' 12Uw2")), vec![Rw(10, Inverse), Uw(12, Double)]);
+}
+
+#[test]
+#[should_panic]
+fn test_invalid_move_rejected() {
+    parse_scramble(String::from("R U Q"));
+}
@@ -0,0 +1,111 @@
+//
+// Scrambler Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, random_state, random_state_scramble, simplify_moves};
+use cubesim::{random_move_scramble, wca_random_move_scramble};
+use cubesim::{Metric, distance_2x2, random_state_2x2, random_state_scramble_2x2};
+
+#[test]
+fn random_state_is_reproducible() {
+    assert!(random_state(7) == random_state(7));
+    assert!(random_state(7) != random_state(8));
+}
+
+#[test]
+fn random_state_is_not_solved() {
+    for seed in 0..20 {
+        assert!(!random_state(seed).is_solved());
+    }
+}
+
+#[test]
+fn random_state_scramble_reaches_random_state() {
+    for seed in 0..3 {
+        let scrambled = FaceletCube::new(3).apply_moves(&random_state_scramble(seed));
+        assert!(scrambled == random_state(seed));
+    }
+}
+
+#[test]
+fn random_state_scramble_is_reproducible() {
+    assert_eq!(random_state_scramble(1), random_state_scramble(1));
+}
+
+#[test]
+fn random_state_2x2_needs_four_moves() {
+    assert!(random_state_2x2(7) == random_state_2x2(7));
+    assert!(random_state_2x2(7) != random_state_2x2(8));
+
+    for seed in 0..20 {
+        assert!(distance_2x2(&random_state_2x2(seed), Metric::HalfTurn).unwrap() >= 4);
+    }
+}
+
+#[test]
+fn random_state_scramble_2x2_reaches_random_state() {
+    for seed in 0..5 {
+        let scramble = random_state_scramble_2x2(seed);
+        let scrambled = FaceletCube::new(2).apply_moves(&scramble);
+
+        assert!(scrambled == random_state_2x2(seed));
+        assert_eq!(distance_2x2(&scrambled, Metric::HalfTurn), Some(scramble.len()));
+    }
+}
+
+#[test]
+fn random_move_scramble_has_requested_length() {
+    for size in 2..=7 {
+        assert_eq!(random_move_scramble(size, 30, 0).len(), 30);
+    }
+}
+
+#[test]
+fn random_move_scramble_is_reproducible() {
+    assert_eq!(random_move_scramble(4, 40, 3), random_move_scramble(4, 40, 3));
+    assert_ne!(random_move_scramble(4, 40, 3), random_move_scramble(4, 40, 4));
+}
+
+#[test]
+fn random_move_scramble_has_no_redundant_moves() {
+    let scramble = random_move_scramble(6, 500, 11);
+
+    assert_eq!(simplify_moves(&scramble), scramble);
+
+    for window in scramble.windows(3) {
+        let faces = window.iter().map(|mv| format!("{:?}", mv).chars().next().unwrap()).collect::<Vec<_>>();
+        let axes = faces.iter().map(|f| match f { 'D' => 'U', 'L' => 'R', 'B' => 'F', f => *f }).collect::<Vec<_>>();
+
+        assert_ne!(faces[0], faces[1]);
+        assert!(!(axes[0] == axes[1] && axes[1] == axes[2]));
+    }
+}
+
+#[test]
+fn random_move_scramble_uses_wide_moves() {
+    let scramble = random_move_scramble(7, 100, 5);
+    assert!(scramble.iter().any(|mv| matches!(mv, Move::Rw(3, _) | Move::Uw(3, _) | Move::Fw(3, _))));
+}
+
+#[test]
+fn random_move_scramble_wide_moves_turn_u_r_f() {
+    for size in 4..=7 {
+        let scramble = random_move_scramble(size, 200, 2);
+        assert!(!scramble.iter().any(|mv| matches!(mv, Move::Dw(_, _) | Move::Lw(_, _) | Move::Bw(_, _))));
+    }
+}
+
+#[test]
+fn random_move_scramble_of_empty_cube() {
+    assert_eq!(random_move_scramble(0, 10, 0), vec![]);
+}
+
+#[test]
+fn wca_random_move_scramble_lengths() {
+    assert_eq!(wca_random_move_scramble(4, 0).unwrap().len(), 40);
+    assert_eq!(wca_random_move_scramble(5, 0).unwrap().len(), 60);
+    assert_eq!(wca_random_move_scramble(6, 0).unwrap().len(), 80);
+    assert_eq!(wca_random_move_scramble(7, 0).unwrap().len(), 100);
+    assert!(wca_random_move_scramble(3, 0).is_none());
+}
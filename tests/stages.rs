@@ -0,0 +1,152 @@
+//
+// Stage Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, GeoCube, all_rotations, parse_scramble};
+use cubesim::{is_cross_solved, f2l_pairs_solved, is_f2l_solved, is_oll_solved, is_pll_solved, is_eo_solved, is_first_block_solved};
+
+const FACES: [Face; 6] = [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B];
+
+const T_PERM: &str = "R U R' U' R' F R2 U' R' U' R U R' F'";
+const SUNE: &str = "R U R' U R U2 R'";
+
+fn cube(scramble: &str) -> FaceletCube {
+    FaceletCube::new(3).apply_moves(&parse_scramble(String::from(scramble)))
+}
+
+#[test]
+fn solved_cube_passes_every_stage() {
+    let cube = FaceletCube::new(3);
+
+    for face in FACES {
+        assert!(is_cross_solved(&cube, face));
+        assert_eq!(f2l_pairs_solved(&cube, face), 4);
+        assert!(is_f2l_solved(&cube, face));
+        assert!(is_oll_solved(&cube, face));
+        assert!(is_pll_solved(&cube, face));
+        assert!(is_eo_solved(&cube, face));
+    }
+}
+
+#[test_case("R U R' U'", Face::D, true ; "cross kept by last layer moves")]
+#[test_case("F", Face::D, false ; "cross broken by side move")]
+#[test_case("D", Face::D, false ; "cross misaligned")]
+#[test_case("D", Face::U, true ; "opposite cross kept")]
+#[test_case("R2 U2 F2", Face::L, false ; "cross on side face")]
+fn cross(scramble: &str, face: Face, solved: bool) {
+    assert_eq!(is_cross_solved(&cube(scramble), face), solved);
+}
+
+#[test_case("", 4 ; "solved")]
+#[test_case("U", 4 ; "last layer turned")]
+#[test_case("R U R'", 3 ; "one pair out")]
+#[test_case("R U R' L' U' L", 2 ; "two pairs out")]
+#[test_case("R U2 R' F' U F", 3 ; "one slot broken twice")]
+#[test_case("D", 0 ; "cross turned")]
+fn f2l_pairs(scramble: &str, pairs: usize) {
+    assert_eq!(f2l_pairs_solved(&cube(scramble), Face::D), pairs);
+    assert_eq!(is_f2l_solved(&cube(scramble), Face::D), pairs == 4);
+}
+
+#[test]
+fn last_layer_stages() {
+    let t_perm = cube(T_PERM);
+    assert!(is_f2l_solved(&t_perm, Face::D));
+    assert!(is_oll_solved(&t_perm, Face::U));
+    assert!(!is_pll_solved(&t_perm, Face::U));
+
+    let sune = cube(SUNE);
+    assert!(is_f2l_solved(&sune, Face::D));
+    assert!(!is_oll_solved(&sune, Face::U));
+    assert!(!is_pll_solved(&sune, Face::U));
+
+    let auf = cube("U2");
+    assert!(is_oll_solved(&auf, Face::U));
+    assert!(is_pll_solved(&auf, Face::U));
+    assert!(!is_pll_solved(&auf, Face::F));
+}
+
+#[test]
+fn oll_needs_first_two_layers() {
+    assert!(!is_oll_solved(&cube("R U R'"), Face::U));
+    assert!(is_oll_solved(&cube("D"), Face::D));
+}
+
+#[test_case(Face::F, "U D L R F2 B2 U' L2 R' D2", true ; "fb axis kept")]
+#[test_case(Face::F, "U R F", false ; "fb axis broken")]
+#[test_case(Face::F, "F2 B'", false ; "fb axis broken by back")]
+#[test_case(Face::R, "U D F B R2 L2 F' U2", true ; "rl axis kept")]
+#[test_case(Face::R, "U R", false ; "rl axis broken")]
+#[test_case(Face::U, "F B R L U2 D2 F'", true ; "ud axis kept")]
+#[test_case(Face::U, "D' F", false ; "ud axis broken")]
+fn edge_orientation(axis: Face, scramble: &str, solved: bool) {
+    assert_eq!(is_eo_solved(&cube(scramble), axis), solved);
+}
+
+#[test]
+fn edge_orientation_of_superflip() {
+    let superflip = cube("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2");
+
+    for face in FACES {
+        assert!(!is_eo_solved(&superflip, face));
+    }
+}
+
+#[test_case(Face::L, Face::D, "R U M' r' R U'", true ; "left block")]
+#[test_case(Face::L, Face::D, "L", false ; "left block turned")]
+#[test_case(Face::R, Face::D, "L U2", true ; "right block")]
+#[test_case(Face::F, Face::U, "B D", true ; "front block on top")]
+#[test_case(Face::F, Face::U, "U", false ; "front block broken")]
+fn first_block(side: Face, bottom: Face, scramble: &str, solved: bool) {
+    let scramble = scramble.replace("M'", "Rw R'").replace("r'", "Rw'");
+    assert_eq!(is_first_block_solved(&cube(&scramble), side, bottom), solved);
+}
+
+#[test]
+#[should_panic]
+fn first_block_needs_adjacent_faces() {
+    is_first_block_solved(&FaceletCube::new(3), Face::L, Face::R);
+}
+
+#[test_case("R U R' U'" ; "last layer")]
+#[test_case(T_PERM ; "t perm")]
+#[test_case(SUNE ; "sune")]
+#[test_case("R U R' L' U' L" ; "two pairs out")]
+#[test_case("Rw U Rw' F" ; "wide moves")]
+fn stages_ignore_orientation(scramble: &str) {
+    let cube = cube(scramble);
+
+    for rotation in all_rotations() {
+        let rotated = cube.apply_moves(&rotation);
+
+        for face in FACES {
+            assert_eq!(is_cross_solved(&rotated, face), is_cross_solved(&cube, face));
+            assert_eq!(f2l_pairs_solved(&rotated, face), f2l_pairs_solved(&cube, face));
+            assert_eq!(is_oll_solved(&rotated, face), is_oll_solved(&cube, face));
+            assert_eq!(is_pll_solved(&rotated, face), is_pll_solved(&cube, face));
+            assert_eq!(is_eo_solved(&rotated, face), is_eo_solved(&cube, face));
+        }
+    }
+}
+
+#[test]
+fn stages_on_geo_cube() {
+    let moves = parse_scramble(String::from(T_PERM));
+    let facelet_cube = FaceletCube::new(3).apply_moves(&moves);
+    let geo_cube = GeoCube::new(3).apply_moves(&moves);
+
+    for face in FACES {
+        assert_eq!(is_cross_solved(&geo_cube, face), is_cross_solved(&facelet_cube, face));
+        assert_eq!(is_oll_solved(&geo_cube, face), is_oll_solved(&facelet_cube, face));
+        assert_eq!(is_pll_solved(&geo_cube, face), is_pll_solved(&facelet_cube, face));
+    }
+}
+
+#[test]
+#[should_panic]
+fn stages_need_3x3x3() {
+    is_cross_solved(&FaceletCube::new(4), Face::D);
+}
@@ -0,0 +1,89 @@
+//
+// Symmetry Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, Symmetry};
+use cubesim::{parse_scramble, random_move_scramble};
+
+#[test]
+fn symmetry_counts() {
+    assert_eq!(Symmetry::rotations().len(), 24);
+    assert_eq!(Symmetry::all().len(), 48);
+}
+
+#[test]
+fn solved_cube_is_symmetric() {
+    for size in 2..=5 {
+        for symmetry in Symmetry::all() {
+            assert!(FaceletCube::new(size).conjugate(&symmetry) == FaceletCube::new(size));
+        }
+    }
+}
+
+#[test]
+fn conjugated_moves_give_conjugated_cube() {
+    for size in 3..=5 {
+        let scramble = random_move_scramble(size, 25, size as u64);
+        let cube = FaceletCube::new(size).apply_moves(&scramble);
+
+        for symmetry in Symmetry::all() {
+            let conjugated_cube = FaceletCube::new(size).apply_moves(&symmetry.conjugate_moves(&scramble));
+            assert!(cube.conjugate(&symmetry) == conjugated_cube);
+        }
+    }
+}
+
+#[test]
+fn conjugated_rotations_give_conjugated_cube() {
+    let scramble = parse_scramble(String::from("R x U y' F2 z L'"));
+    let cube = FaceletCube::new(3).apply_moves(&scramble);
+
+    for symmetry in Symmetry::all() {
+        let conjugated_cube = FaceletCube::new(3).apply_moves(&symmetry.conjugate_moves(&scramble));
+        assert!(cube.conjugate(&symmetry) == conjugated_cube);
+    }
+}
+
+#[test]
+fn inverse_symmetry() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U2 F' L D B2")));
+
+    for symmetry in Symmetry::all() {
+        assert!(cube.conjugate(&symmetry).conjugate(&symmetry.inverse()) == cube);
+    }
+}
+
+#[test]
+fn canonical_form_is_shared_by_symmetric_cubes() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R' U R U2 R'")));
+    let (canonical, symmetry) = cube.canonical_form(&Symmetry::all());
+
+    assert!(cube.conjugate(&symmetry) == canonical);
+
+    for other in Symmetry::all() {
+        assert!(cube.conjugate(&other).canonical_form(&Symmetry::all()).0.state() == canonical.state());
+    }
+}
+
+#[test]
+fn rotations_do_not_identify_mirrored_cubes() {
+    let sune = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R' U R U2 R'")));
+    let mirrored_sune = sune.conjugate(&Symmetry::new(vec![], true));
+
+    assert!(sune.canonical_form(&Symmetry::rotations()).0.state() != mirrored_sune.canonical_form(&Symmetry::rotations()).0.state());
+    assert!(sune.canonical_form(&Symmetry::all()).0.state() == mirrored_sune.canonical_form(&Symmetry::all()).0.state());
+}
+
+#[test]
+fn face_maps() {
+    let x = Symmetry::new(parse_scramble(String::from("x")), false);
+    assert_eq!(x.face_map(Face::F), Face::U);
+    assert_eq!(x.face_map(Face::R), Face::R);
+    assert_eq!(x.face_map(Face::X), Face::X);
+
+    let mirror = Symmetry::new(vec![], true);
+    assert_eq!(mirror.face_map(Face::L), Face::R);
+    assert_eq!(mirror.inverse(), mirror);
+    assert!(mirror.reflection() && mirror.rotation().is_empty());
+}
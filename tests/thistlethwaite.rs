@@ -5,7 +5,7 @@
 //
 
 use cubesim::prelude::*;
-use cubesim::{GeoCube, FaceletCube};
+use cubesim::FaceletCube;
 use cubesim::solve;
 use cubesim::parse_scramble;
 
@@ -35,3 +35,19 @@ fn superflip() {
     assert!(cube.apply_moves(&solve(&cube).unwrap()).is_solved());
 }
 
+#[test]
+fn rotated_scramble() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("x R U F' y D2 L z'")));
+    let solution = solve(&cube).unwrap();
+
+    assert!(matches!(solution[0], Move::X(_) | Move::Y(_) | Move::Z(_)));
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn no_rotations_in_standard_orientation() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F' D2 L")));
+    let solution = solve(&cube).unwrap();
+
+    assert!(solution.iter().all(|mv| !matches!(mv, Move::X(_) | Move::Y(_) | Move::Z(_))));
+}